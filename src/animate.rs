@@ -0,0 +1,300 @@
+//! Playing a simulation in the terminal one frame at a time, and recording the frames as an
+//! asciicast (v2) file that `asciinema play` can replay.
+//!
+//! Days draw each frame as plain text; the animator colours it one character at a time with the
+//! day's `Palette`, so the same drawing works with colour turned off.
+use crate::simulation::{Driver, Simulation};
+use serde_json::json;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+/// Set to turn colour off, as in <https://no-color.org>.
+pub const NO_COLOR_VAR: &str = "NO_COLOR";
+
+const CLEAR: &str = "\x1b[H\x1b[2J";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Grey,
+}
+
+impl Colour {
+    fn code(self) -> &'static str {
+        match self {
+            Colour::Red => "\x1b[31m",
+            Colour::Green => "\x1b[32m",
+            Colour::Yellow => "\x1b[33m",
+            Colour::Blue => "\x1b[34m",
+            Colour::Magenta => "\x1b[35m",
+            Colour::Cyan => "\x1b[36m",
+            Colour::White => "\x1b[97m",
+            Colour::Grey => "\x1b[90m",
+        }
+    }
+}
+
+/// The colour to draw each character of a frame in, if any.
+pub type Palette = fn(char) -> Option<Colour>;
+
+/// `text` with every character coloured by `palette`, starting a new escape only where the colour
+/// changes.
+pub fn paint(text: &str, palette: Palette) -> String {
+    let mut painted = String::with_capacity(text.len());
+    let mut current = None;
+    for ch in text.chars() {
+        let colour = if ch == '\n' { None } else { palette(ch) };
+        if colour != current {
+            painted += colour.map_or(RESET, Colour::code);
+            current = colour;
+        }
+        painted.push(ch);
+    }
+    if current.is_some() {
+        painted += RESET;
+    }
+    painted
+}
+
+/// Frames as an asciicast v2 recording: a JSON header line, then one `[time, "o", data]` event
+/// per frame.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recording {
+    pub width: usize,
+    pub height: usize,
+    /// Seconds from the start, and what was written to the terminal then.
+    pub events: Vec<(f64, String)>,
+}
+
+impl Recording {
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))
+    }
+}
+
+impl fmt::Display for Recording {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{{\"version\": 2, \"width\": {}, \"height\": {}}}",
+            self.width, self.height
+        )?;
+        for (time, data) in &self.events {
+            writeln!(f, "{}", json!([time, "o", data]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Shows frames in the terminal at a steady rate, recording them as it goes if asked to.
+///
+/// Recordings are timed by frame count rather than the clock, so a recording made with playback
+/// off comes out the same as one made watching.
+pub struct Animator {
+    delay: Duration,
+    palette: Option<Palette>,
+    colour: bool,
+    play: bool,
+    recording: Option<Recording>,
+    frames: usize,
+    cursor: Option<HiddenCursor>,
+}
+
+impl Animator {
+    /// An animator showing `fps` frames a second, in colour unless `NO_COLOR` is set.
+    pub fn new(fps: f64) -> Animator {
+        Animator {
+            delay: Duration::from_secs_f64(1.0 / fps.max(0.001)),
+            palette: None,
+            colour: std::env::var_os(NO_COLOR_VAR).is_none(),
+            play: true,
+            recording: None,
+            frames: 0,
+            cursor: None,
+        }
+    }
+
+    pub fn colour(mut self, colour: bool) -> Animator {
+        self.colour = colour;
+        self
+    }
+
+    /// Whether to show frames in the terminal. Without it frames are only recorded, as fast as
+    /// they're drawn.
+    pub fn play(mut self, play: bool) -> Animator {
+        self.play = play;
+        self
+    }
+
+    /// Keep every frame, for `recording()`.
+    pub fn record(mut self) -> Animator {
+        self.recording = Some(Recording::default());
+        self
+    }
+
+    /// Colour frames with `palette` from now on. Days set their own.
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = Some(palette);
+    }
+
+    pub fn recording(&self) -> Option<&Recording> {
+        self.recording.as_ref()
+    }
+
+    /// Frames shown so far.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Show the next frame, then wait until it's time for the one after.
+    pub fn show(&mut self, frame: &str) -> io::Result<()> {
+        let frame = match (self.colour, self.palette) {
+            (true, Some(palette)) => paint(frame, palette),
+            _ => frame.to_string(),
+        };
+        // the terminal gets the text as is, so a newline alone wouldn't go back to the first column
+        let data = format!("{}{}\r\n", CLEAR, frame.replace('\n', "\r\n"));
+
+        if let Some(recording) = &mut self.recording {
+            let lines = frame.lines();
+            recording.height = recording.height.max(lines.clone().count() + 1);
+            let width = lines.map(visible_width).max().unwrap_or(0);
+            recording.width = recording.width.max(width);
+            // counted in whole microseconds so the times don't pick up rounding noise
+            let time = (self.frames as u128 * self.delay.as_micros()) as f64 / 1e6;
+            recording.events.push((time, data.clone()));
+        }
+        if self.play {
+            let mut stdout = io::stdout().lock();
+            if self.cursor.is_none() {
+                self.cursor = Some(HiddenCursor::hide(&mut stdout)?);
+            }
+            stdout.write_all(data.as_bytes())?;
+            stdout.flush()?;
+            thread::sleep(self.delay);
+        }
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Run `driver` to the end, showing the state before the first step and after every one.
+    pub fn play_driver<S: Simulation>(
+        &mut self,
+        mut driver: Driver<S>,
+        render: impl Fn(&S) -> String,
+    ) -> Result<S, Box<dyn Error>> {
+        self.show(&render(driver.sim()))?;
+        while driver.step()? {
+            self.show(&render(driver.sim()))?;
+        }
+        Ok(driver.into_inner())
+    }
+
+    /// Put the terminal back the way it was. Dropping the animator does too, but can't report
+    /// an error doing it.
+    pub fn finish(&mut self) -> io::Result<()> {
+        match self.cursor.take() {
+            Some(cursor) => cursor.show(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The terminal's cursor while frames are playing: hidden until this is shown or dropped, so a
+/// panic part way through doesn't leave it hidden.
+struct HiddenCursor;
+
+impl HiddenCursor {
+    fn hide(stdout: &mut impl Write) -> io::Result<HiddenCursor> {
+        stdout.write_all(HIDE_CURSOR.as_bytes())?;
+        Ok(HiddenCursor)
+    }
+
+    fn show(self) -> io::Result<()> {
+        std::mem::forget(self);
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(SHOW_CURSOR.as_bytes())?;
+        stdout.flush()
+    }
+}
+
+impl Drop for HiddenCursor {
+    fn drop(&mut self) {
+        let mut stdout = io::stdout().lock();
+        // there's nothing more to be done if the terminal has gone away
+        let _ = stdout
+            .write_all(SHOW_CURSOR.as_bytes())
+            .and_then(|()| stdout.flush());
+    }
+}
+
+/// Characters in `line` once colour escapes are left out.
+fn visible_width(line: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for ch in line.chars() {
+        match ch {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if !in_escape => width += 1,
+            _ => {}
+        }
+    }
+    width
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette(ch: char) -> Option<Colour> {
+        match ch {
+            '#' => Some(Colour::Red),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_paint() {
+        assert_eq!(paint("..", palette), "..");
+        assert_eq!(
+            paint(".##\n#", palette),
+            ".\x1b[31m##\x1b[0m\n\x1b[31m#\x1b[0m"
+        );
+        assert_eq!(visible_width(&paint(".##", palette)), 3);
+    }
+
+    #[test]
+    fn test_recording() {
+        let mut animator = Animator::new(4.0).play(false).colour(false).record();
+        animator.set_palette(palette);
+        animator.show("#.\n..").unwrap();
+        animator.show("###").unwrap();
+        animator.finish().unwrap();
+        let recording = animator.recording().unwrap();
+        assert_eq!((recording.width, recording.height), (3, 3));
+        assert_eq!(
+            recording.to_string(),
+            concat!(
+                "{\"version\": 2, \"width\": 3, \"height\": 3}\n",
+                "[0.0,\"o\",\"\\u001b[H\\u001b[2J#.\\r\\n..\\r\\n\"]\n",
+                "[0.25,\"o\",\"\\u001b[H\\u001b[2J###\\r\\n\"]\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,239 @@
+use crate::input::InputSource;
+use crate::registry;
+use crate::solution::Part;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// An answer we know to be right for one day, part and input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub day: u32,
+    pub part: Part,
+    pub input: PathBuf,
+    pub answer: String,
+}
+
+/// The checked-in list of known answers. One tab separated `day part input answer` record per
+/// line; input paths are relative to the crate root, and a newline inside an answer is written as
+/// `\n`. Blank lines and lines starting with `#` are ignored.
+#[derive(Debug, Default)]
+pub struct Manifest {
+    pub entries: Vec<Expected>,
+}
+
+impl Manifest {
+    pub fn default_path() -> PathBuf {
+        crate_root().join("src/answers.txt")
+    }
+
+    pub fn load(path: &Path) -> Result<Manifest, Box<dyn Error>> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("could not read {}: {}", path.display(), err))?;
+        Manifest::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Manifest, Box<dyn Error>> {
+        let mut entries = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields = line.split('\t').collect::<Vec<&str>>();
+            let [day, part, input, answer] = fields[..] else {
+                return Err(format!("line {}: expected 4 tab separated fields", i + 1))?;
+            };
+            let day = day
+                .parse::<u32>()
+                .map_err(|_| format!("line {}: invalid day {:?}", i + 1, day))?;
+            let part = match part {
+                "1" => Part::One,
+                "2" => Part::Two,
+                _ => return Err(format!("line {}: invalid part {:?}", i + 1, part))?,
+            };
+            entries.push(Expected {
+                day,
+                part,
+                input: resolve(Path::new(input)),
+                answer: answer.replace("\\n", "\n"),
+            });
+        }
+        Ok(Manifest { entries })
+    }
+
+    fn expected(&self, day: u32, part: Part, input: &Path) -> Option<&Expected> {
+        self.entries
+            .iter()
+            .find(|e| e.day == day && e.part == part && e.input == input)
+    }
+}
+
+fn crate_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn resolve(path: &Path) -> PathBuf {
+    if path.is_relative() {
+        crate_root().join(path)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// There is no known answer to compare against.
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Status::Pass => "PASS",
+            Status::Fail => "FAIL",
+            Status::Missing => "MISSING",
+        };
+        f.pad(s)
+    }
+}
+
+#[derive(Debug)]
+pub struct Checked {
+    pub day: u32,
+    pub part: Part,
+    pub input: PathBuf,
+    pub status: Status,
+    pub expected: Option<String>,
+    /// The solver's answer, or the error it failed with.
+    pub actual: Result<String, String>,
+}
+
+impl fmt::Display for Checked {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let actual = match &self.actual {
+            Ok(answer) => format!("{:?}", answer),
+            Err(err) => format!("error: {}", err),
+        };
+        write!(
+            f,
+            "{:<7} day {:02} part {} ({}): ",
+            self.status,
+            self.day,
+            self.part,
+            self.input
+                .strip_prefix(crate_root())
+                .unwrap_or(&self.input)
+                .display()
+        )?;
+        match (&self.status, &self.expected) {
+            (Status::Fail, Some(expected)) => {
+                write!(f, "expected {:?}, got {}", expected, actual)
+            }
+            _ => write!(f, "{}", actual),
+        }
+    }
+}
+
+/// Run every registered solver against each input the manifest knows about (or its usual input
+/// if it knows none), plus any manifest entries for days that aren't registered.
+pub fn check(manifest: &Manifest) -> Vec<Checked> {
+    let mut results = Vec::new();
+
+    for solution in registry::all() {
+        let day = solution.day();
+        let mut inputs: Vec<PathBuf> = vec![];
+        for entry in manifest.entries.iter().filter(|e| e.day == day) {
+            if !inputs.contains(&entry.input) {
+                inputs.push(entry.input.clone());
+            }
+        }
+        if inputs.is_empty() {
+            match InputSource::for_day(day, None) {
+                InputSource::File(path) => inputs.push(path),
+                InputSource::Stdin => unreachable!("inputs are never read from stdin by default"),
+            }
+        }
+
+        for input in inputs {
+            let parsed = InputSource::File(input.clone())
+                .read()
+                .map_err(|err| err.to_string())
+                .and_then(|content| solution.parse(&content).map_err(|err| err.to_string()));
+            for part in Part::BOTH {
+                let actual = match &parsed {
+                    Ok(parsed) => parsed
+                        .part(part)
+                        .map(|answer| answer.to_string())
+                        .map_err(|err| err.to_string()),
+                    Err(err) => Err(err.clone()),
+                };
+                let expected = manifest
+                    .expected(day, part, &input)
+                    .map(|e| e.answer.clone());
+                let status = match (&expected, &actual) {
+                    (None, _) => Status::Missing,
+                    (Some(expected), Ok(actual)) if expected == actual => Status::Pass,
+                    (Some(_), _) => Status::Fail,
+                };
+                results.push(Checked {
+                    day,
+                    part,
+                    input: input.clone(),
+                    status,
+                    expected,
+                    actual,
+                });
+            }
+        }
+    }
+
+    for entry in &manifest.entries {
+        if registry::get(entry.day).is_none() {
+            results.push(Checked {
+                day: entry.day,
+                part: entry.part,
+                input: entry.input.clone(),
+                status: Status::Fail,
+                expected: Some(entry.answer.clone()),
+                actual: Err(format!("day {} is not registered", entry.day)),
+            });
+        }
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = Manifest::parse(
+            "# day\tpart\tinput\tanswer\n\n5\t1\tsrc/d05/input\tCMZ\n10\t2\t/tmp/x\t##\\n..\n",
+        )
+        .unwrap();
+        assert_eq!(
+            manifest.entries,
+            vec![
+                Expected {
+                    day: 5,
+                    part: Part::One,
+                    input: crate_root().join("src/d05/input"),
+                    answer: String::from("CMZ"),
+                },
+                Expected {
+                    day: 10,
+                    part: Part::Two,
+                    input: PathBuf::from("/tmp/x"),
+                    answer: String::from("##\n.."),
+                },
+            ]
+        );
+        assert!(Manifest::parse("5\t3\tsrc/d05/input\tCMZ").is_err());
+        assert!(Manifest::parse("5\t1\tCMZ").is_err());
+    }
+}
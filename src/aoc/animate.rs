@@ -0,0 +1,103 @@
+use advent_of_code_2022::animate::Animator;
+use advent_of_code_2022::input::InputSource;
+use advent_of_code_2022::registry;
+use advent_of_code_2022::solution::Part;
+use std::error::Error;
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: aoc animate --day <n> [--part 1|2] [--input <path>|-] [--fps <n>]
+                   [--record <path>] [--no-play] [--no-colour]";
+
+struct AnimateArgs {
+    day: u32,
+    part: Part,
+    input: Option<String>,
+    fps: f64,
+    record: Option<PathBuf>,
+    play: bool,
+    colour: bool,
+}
+
+fn parse_args(args: &[String]) -> Result<AnimateArgs, String> {
+    let mut day = None;
+    let mut part = Part::One;
+    let mut input = None;
+    let mut fps = 10.0;
+    let mut record = None;
+    let mut play = true;
+    let mut colour = true;
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--day" => {
+                let value = value()?;
+                day = Some(
+                    value
+                        .parse::<u32>()
+                        .map_err(|_| format!("invalid day {}", value))?,
+                );
+            }
+            "--part" => {
+                part = match value()?.as_str() {
+                    "1" => Part::One,
+                    "2" => Part::Two,
+                    value => return Err(format!("--part must be 1 or 2, got {}", value)),
+                };
+            }
+            "--input" => input = Some(value()?.clone()),
+            "--fps" => {
+                let value = value()?;
+                fps = match value.parse::<f64>() {
+                    Ok(fps) if fps > 0.0 => fps,
+                    _ => return Err(format!("--fps must be a positive number, got {}", value)),
+                };
+            }
+            "--record" => record = Some(PathBuf::from(value()?)),
+            "--no-play" => play = false,
+            "--no-colour" | "--no-color" => colour = false,
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+
+    let day = day.ok_or("--day is required")?;
+    if !play && record.is_none() {
+        return Err(String::from("--no-play only makes sense with --record"));
+    }
+    Ok(AnimateArgs {
+        day,
+        part,
+        input,
+        fps,
+        record,
+        play,
+        colour,
+    })
+}
+
+/// `aoc animate`: play a day's simulation in the terminal, `--fps` frames a second, and
+/// optionally save it as an asciicast for `asciinema play`.
+pub fn main(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let args = parse_args(args).map_err(|e| format!("{}\n{}", e, USAGE))?;
+    let solution = registry::get(args.day).ok_or(format!("day {} is not implemented", args.day))?;
+    let content = InputSource::for_day(args.day, args.input.as_deref()).read()?;
+    let parsed = solution.parse(&content)?;
+
+    let mut animator = Animator::new(args.fps).play(args.play);
+    if !args.colour {
+        animator = animator.colour(false);
+    }
+    if args.record.is_some() {
+        animator = animator.record();
+    }
+    let result = parsed.animate(args.part, &mut animator);
+    animator.finish()?;
+    result?;
+
+    if let (Some(path), Some(recording)) = (&args.record, animator.recording()) {
+        recording.save(path)?;
+        println!("{} frames saved to {}", animator.frames(), path.display());
+    }
+    Ok(())
+}
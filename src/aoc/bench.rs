@@ -0,0 +1,128 @@
+use advent_of_code_2022::bench::{self, Report};
+use advent_of_code_2022::input::InputSource;
+use advent_of_code_2022::registry;
+use std::error::Error;
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: aoc bench (--day <n> [--input <path>|-] | --all) [--iterations <n>]
+                 [--output <path>] [--baseline <path>] [--threshold <percent>]";
+
+struct BenchArgs {
+    days: Vec<u32>,
+    input: Option<String>,
+    iterations: usize,
+    output: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+}
+
+fn parse_args(args: &[String]) -> Result<BenchArgs, String> {
+    let mut day = None;
+    let mut all = false;
+    let mut input = None;
+    let mut iterations = 10;
+    let mut output = None;
+    let mut baseline = None;
+    let mut threshold = 10.0;
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--day" => {
+                let value = value()?;
+                day = Some(
+                    value
+                        .parse::<u32>()
+                        .map_err(|_| format!("invalid day {}", value))?,
+                );
+            }
+            "--all" => all = true,
+            "--input" => input = Some(value()?.clone()),
+            "--iterations" => {
+                let value = value()?;
+                iterations = match value.parse::<usize>() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(format!("--iterations must be at least 1, got {}", value)),
+                };
+            }
+            "--output" => output = Some(PathBuf::from(value()?)),
+            "--baseline" => baseline = Some(PathBuf::from(value()?)),
+            "--threshold" => {
+                let value = value()?;
+                threshold = value
+                    .parse::<f64>()
+                    .map_err(|_| format!("invalid threshold {}", value))?;
+            }
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+
+    let days = match (day, all) {
+        (Some(n), false) => vec![n],
+        (None, true) => registry::all().iter().map(|s| s.day()).collect(),
+        _ => return Err(String::from("pass exactly one of --day or --all")),
+    };
+    if all && input.is_some() {
+        return Err(String::from("--input can only be used with a single --day"));
+    }
+    Ok(BenchArgs {
+        days,
+        input,
+        iterations,
+        output,
+        baseline,
+        threshold,
+    })
+}
+
+/// `aoc bench`: time parse, part 1 and part 2 for each day and print min/median/max in
+/// milliseconds. Optionally save the results as JSON, and fail if any median regressed against a
+/// saved baseline by more than the threshold.
+pub fn main(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let args = parse_args(args).map_err(|e| format!("{}\n{}", e, USAGE))?;
+    let baseline = args.baseline.as_deref().map(Report::load).transpose()?;
+
+    let mut report = Report {
+        iterations: args.iterations,
+        days: vec![],
+    };
+    for n in args.days {
+        let solution = registry::get(n).ok_or(format!("day {} is not implemented", n))?;
+        let content = InputSource::for_day(n, args.input.as_deref()).read()?;
+        report
+            .days
+            .push(bench::bench(solution, &content, args.iterations)?);
+    }
+
+    println!(
+        "{:<6} {:<6} {:>10} {:>10} {:>10}",
+        "day", "phase", "min ms", "median ms", "max ms"
+    );
+    for day in &report.days {
+        for (phase, stats) in day.phases() {
+            match stats {
+                Some(stats) => println!("{:<6} {:<6} {}", day.day, phase, stats),
+                None => println!("{:<6} {:<6} {:>10}", day.day, phase, "-"),
+            }
+        }
+    }
+
+    if let Some(path) = &args.output {
+        report.save(path)?;
+    }
+    if let Some(baseline) = baseline {
+        let regressions = bench::regressions(&baseline, &report, args.threshold / 100.0);
+        for regression in &regressions {
+            println!("regression: {}", regression);
+        }
+        if !regressions.is_empty() {
+            Err(format!(
+                "{} phases regressed by more than {}%",
+                regressions.len(),
+                args.threshold
+            ))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use advent_of_code_2022::answers::{self, Manifest, Status};
+use std::error::Error;
+use std::path::PathBuf;
+
+/// `aoc check [--manifest <path>]`: run every solver and compare with the known answers.
+pub fn main(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let path = match args {
+        [] => Manifest::default_path(),
+        [flag, path] if flag == "--manifest" => PathBuf::from(path),
+        _ => return Err("usage: aoc check [--manifest <path>]")?,
+    };
+    let manifest = Manifest::load(&path)?;
+
+    let results = answers::check(&manifest);
+    for result in &results {
+        println!("{}", result);
+    }
+    let count = |status| results.iter().filter(|r| r.status == status).count();
+    let failed = count(Status::Fail);
+    println!(
+        "{} passed, {} failed, {} missing",
+        count(Status::Pass),
+        failed,
+        count(Status::Missing)
+    );
+    if failed > 0 {
+        Err(format!("{} answers did not match", failed))?;
+    }
+    Ok(())
+}
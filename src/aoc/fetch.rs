@@ -0,0 +1,27 @@
+use advent_of_code_2022::client::{self, Fetched};
+use advent_of_code_2022::config::Config;
+use advent_of_code_2022::input::InputSource;
+use std::error::Error;
+
+const USAGE: &str = "usage: aoc fetch --day <n>";
+
+/// `aoc fetch --day <n>`: download a day's input to wherever `aoc run` will look for it, unless
+/// it's already there.
+pub fn main(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let day = match args {
+        [flag, value] if flag == "--day" => value
+            .parse::<u32>()
+            .map_err(|_| format!("invalid day {}\n{}", value, USAGE))?,
+        _ => return Err(USAGE)?,
+    };
+    let path = match InputSource::for_day(day, None) {
+        InputSource::File(path) => path,
+        InputSource::Stdin => unreachable!("inputs are never read from stdin by default"),
+    };
+
+    match client::fetch(&Config::load()?, day, &path)? {
+        Fetched::Cached(path) => println!("day {:02}: already have {}", day, path.display()),
+        Fetched::Downloaded(path) => println!("day {:02}: saved {}", day, path.display()),
+    }
+    Ok(())
+}
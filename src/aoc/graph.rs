@@ -0,0 +1,60 @@
+use advent_of_code_2022::input::InputSource;
+use advent_of_code_2022::registry;
+use advent_of_code_2022::solution::Part;
+use std::error::Error;
+use std::io::{self, ErrorKind, Write};
+
+const USAGE: &str = "usage: aoc graph --day <n> [--part 1|2] [--input <path>|-] > out.dot";
+
+struct GraphArgs {
+    day: u32,
+    part: Part,
+    input: Option<String>,
+}
+
+fn parse_args(args: &[String]) -> Result<GraphArgs, String> {
+    let mut day = None;
+    let mut part = Part::One;
+    let mut input = None;
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--day" => {
+                let value = value()?;
+                day = Some(
+                    value
+                        .parse::<u32>()
+                        .map_err(|_| format!("invalid day {}", value))?,
+                );
+            }
+            "--part" => {
+                part = match value()?.as_str() {
+                    "1" => Part::One,
+                    "2" => Part::Two,
+                    value => return Err(format!("--part must be 1 or 2, got {}", value)),
+                };
+            }
+            "--input" => input = Some(value()?.clone()),
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+
+    let day = day.ok_or("--day is required")?;
+    Ok(GraphArgs { day, part, input })
+}
+
+/// `aoc graph`: print a day's model as Graphviz DOT, for `dot -Tsvg`.
+pub fn main(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let args = parse_args(args).map_err(|e| format!("{}\n{}", e, USAGE))?;
+    let solution = registry::get(args.day).ok_or(format!("day {} is not implemented", args.day))?;
+    let content = InputSource::for_day(args.day, args.input.as_deref()).read()?;
+    let parsed = solution.parse(&content)?;
+    let dot = parsed.graph(args.part)?.to_string();
+    // piping into something like `head` that stops reading early isn't an error
+    match io::stdout().lock().write_all(dot.as_bytes()) {
+        Err(err) if err.kind() == ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
@@ -0,0 +1,108 @@
+use advent_of_code_2022::image::{self, ImageFormat};
+use advent_of_code_2022::input::InputSource;
+use advent_of_code_2022::registry;
+use advent_of_code_2022::solution::Part;
+use std::error::Error;
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: aoc image --day <n> [--part 1|2] [--input <path>|-] [--scale <n>]
+                 --output <path>.ppm|<path>.png";
+
+struct ImageArgs {
+    day: u32,
+    part: Part,
+    input: Option<String>,
+    scale: usize,
+    output: PathBuf,
+    format: ImageFormat,
+}
+
+fn parse_args(args: &[String]) -> Result<ImageArgs, String> {
+    let mut day = None;
+    let mut part = Part::One;
+    let mut input = None;
+    let mut scale = 4;
+    let mut output = None;
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().ok_or(format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--day" => {
+                let value = value()?;
+                day = Some(
+                    value
+                        .parse::<u32>()
+                        .map_err(|_| format!("invalid day {}", value))?,
+                );
+            }
+            "--part" => {
+                part = match value()?.as_str() {
+                    "1" => Part::One,
+                    "2" => Part::Two,
+                    value => return Err(format!("--part must be 1 or 2, got {}", value)),
+                };
+            }
+            "--input" => input = Some(value()?.clone()),
+            "--scale" => {
+                let value = value()?;
+                scale = match value.parse::<usize>() {
+                    Ok(scale) if scale > 0 => scale,
+                    _ => {
+                        return Err(format!(
+                            "--scale must be a positive whole number, got {}",
+                            value
+                        ))
+                    }
+                };
+            }
+            "--output" => output = Some(PathBuf::from(value()?)),
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+
+    let day = day.ok_or("--day is required")?;
+    let output = output.ok_or("--output is required")?;
+    let format = ImageFormat::from_path(&output)
+        .ok_or_else(|| format!("{} should end in .ppm or .png", output.display()))?;
+    Ok(ImageArgs {
+        day,
+        part,
+        input,
+        scale,
+        output,
+        format,
+    })
+}
+
+/// `aoc image`: draw a day's grid as a PPM or PNG, `--scale` pixels to a cell. Days that draw a
+/// frame per step get numbered files next to `--output` instead.
+pub fn main(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let args = parse_args(args).map_err(|e| format!("{}\n{}", e, USAGE))?;
+    let solution = registry::get(args.day).ok_or(format!("day {} is not implemented", args.day))?;
+    let content = InputSource::for_day(args.day, args.input.as_deref()).read()?;
+    let parsed = solution.parse(&content)?;
+
+    let frames = parsed
+        .images(args.part)?
+        .iter()
+        .map(|frame| frame.scaled(args.scale))
+        .collect::<Vec<_>>();
+    match &frames[..] {
+        [] => Err(format!("day {} drew nothing", args.day))?,
+        [frame] => {
+            frame.save(&args.output, args.format)?;
+            println!("saved {}", args.output.display());
+        }
+        _ => {
+            let paths = image::save_frames(&frames, &args.output, args.format)?;
+            println!(
+                "{} frames saved to {} .. {}",
+                paths.len(),
+                paths[0].display(),
+                paths[paths.len() - 1].display()
+            );
+        }
+    }
+    Ok(())
+}
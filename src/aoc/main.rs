@@ -0,0 +1,173 @@
+mod animate;
+mod bench;
+mod check;
+mod fetch;
+mod graph;
+mod image;
+mod new;
+mod submit;
+
+use advent_of_code_2022::input::InputSource;
+use advent_of_code_2022::params::Params;
+use advent_of_code_2022::registry;
+use advent_of_code_2022::runner::{self, Output};
+use advent_of_code_2022::solution::Part;
+use std::error::Error;
+
+fn parse_parts(s: &str) -> Result<Vec<Part>, String> {
+    match s {
+        "1" => Ok(vec![Part::One]),
+        "2" => Ok(vec![Part::Two]),
+        "both" => Ok(Part::BOTH.to_vec()),
+        _ => Err(format!("--part must be 1, 2 or both, got {}", s)),
+    }
+}
+
+const USAGE: &str = "usage: aoc run (--day <n> [--input <path>|-] | --all) [--part 1|2|both]
+                 [--set <name>[.<part>]=<value>]... [--format text|json] [--explain] [-v|-vv]
+                 [--trace-json]
+       aoc check [--manifest <path>]
+       aoc animate --day <n> [--part 1|2] [--input <path>|-] [--fps <n>] [--record <path>]
+                   [--no-play] [--no-colour]
+       aoc image --day <n> [--part 1|2] [--input <path>|-] [--scale <n>]
+                 --output <path>.ppm|<path>.png
+       aoc graph --day <n> [--part 1|2] [--input <path>|-] > out.dot
+       aoc bench (--day <n> [--input <path>|-] | --all) [--iterations <n>] [--output <path>]
+                 [--baseline <path>] [--threshold <percent>]
+       aoc fetch --day <n>
+       aoc submit --day <n> --part 1|2 [--input <path>|-]
+       aoc new <day>";
+
+struct RunArgs {
+    days: Vec<u32>,
+    input: Option<String>,
+    parts: Vec<Part>,
+    output: Output,
+    explain: bool,
+    /// `name=value` overrides of the day's parameters.
+    settings: Vec<String>,
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    let mut day = None;
+    let mut all = false;
+    let mut input = None;
+    let mut parts = Part::BOTH.to_vec();
+    let mut output = Output::Text;
+    let mut explain = false;
+    let mut settings = vec![];
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--day" => {
+                let value = it.next().ok_or("--day needs a value")?;
+                let n = value
+                    .parse::<u32>()
+                    .map_err(|_| format!("invalid day {}", value))?;
+                day = Some(n);
+            }
+            "--part" => {
+                let value = it.next().ok_or("--part needs a value")?;
+                parts = parse_parts(value)?;
+            }
+            "--input" => {
+                let value = it.next().ok_or("--input needs a path, or - for stdin")?;
+                input = Some(value.clone());
+            }
+            "--format" => {
+                output = match it.next().map(String::as_str) {
+                    Some("text") => Output::Text,
+                    Some("json") => Output::Json,
+                    _ => return Err(String::from("--format must be text or json")),
+                };
+            }
+            "--set" => {
+                let value = it.next().ok_or("--set needs a name=value")?;
+                settings.push(value.clone());
+            }
+            "--explain" => explain = true,
+            "--all" => all = true,
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+
+    let days = match (day, all) {
+        (Some(n), false) => vec![n],
+        (None, true) => registry::all().iter().map(|s| s.day()).collect(),
+        _ => return Err(String::from("pass exactly one of --day or --all")),
+    };
+    if all && input.is_some() {
+        return Err(String::from("--input can only be used with a single --day"));
+    }
+    if all && !settings.is_empty() {
+        return Err(String::from("--set can only be used with a single --day"));
+    }
+    Ok(RunArgs {
+        days,
+        input,
+        parts,
+        output,
+        explain,
+        settings,
+    })
+}
+
+/// Run one day, returning how many of its parts failed.
+fn run_day(n: u32, args: &RunArgs) -> Result<usize, Box<dyn Error>> {
+    let solution = registry::get(n).ok_or(format!("day {} is not implemented", n))?;
+    let params = Params::set(solution.params(), &args.settings)?;
+    let input = InputSource::for_day(n, args.input.as_deref());
+    runner::run(
+        solution,
+        &input,
+        params,
+        &args.parts,
+        args.output,
+        args.explain,
+    )
+}
+
+/// Run each day in turn, failing at the end if any part did. With `--all`, a day that can't be
+/// run (a missing input, say) is reported and the rest still run.
+fn run(args: RunArgs) -> Result<(), Box<dyn Error>> {
+    let mut failed_days = 0;
+    let mut failed_parts = 0;
+    for &n in &args.days {
+        match run_day(n, &args) {
+            Ok(failed) => failed_parts += failed,
+            Err(err) if args.days.len() > 1 && !runner::is_broken_pipe(err.as_ref()) => {
+                eprintln!("error: day {:02}: {}", n, err);
+                failed_days += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    if failed_days > 0 {
+        Err(format!(
+            "{} of {} days failed",
+            failed_days,
+            args.days.len()
+        ))?;
+    }
+    runner::parts_failed(failed_parts)
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("run") => parse_run_args(&runner::init_tracing(&args[1..]))
+            .map_err(|e| format!("{}\n{}", e, USAGE).into())
+            .and_then(run),
+        Some("check") => check::main(&args[1..]),
+        Some("animate") => animate::main(&args[1..]),
+        Some("image") => image::main(&args[1..]),
+        Some("graph") => graph::main(&args[1..]),
+        Some("bench") => bench::main(&args[1..]),
+        Some("fetch") => fetch::main(&args[1..]),
+        Some("submit") => submit::main(&args[1..]),
+        Some("new") => new::main(&args[1..]),
+        _ => Err(USAGE.into()),
+    };
+    runner::exit_on_error(result);
+}
@@ -0,0 +1,18 @@
+use advent_of_code_2022::scaffold;
+use std::error::Error;
+use std::path::Path;
+
+/// `aoc new <day>`: start a new day from the template and register it.
+pub fn main(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let day = match args {
+        [day] => day
+            .parse::<u32>()
+            .map_err(|_| format!("invalid day {}", day))?,
+        _ => return Err("usage: aoc new <day>")?,
+    };
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    for path in scaffold::new_day(root, day)? {
+        println!("{}", path.strip_prefix(root).unwrap_or(&path).display());
+    }
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use advent_of_code_2022::client::Client;
+use advent_of_code_2022::config::Config;
+use advent_of_code_2022::input::InputSource;
+use advent_of_code_2022::registry;
+use advent_of_code_2022::solution::Part;
+use advent_of_code_2022::submit::{Guess, History};
+use std::error::Error;
+
+const USAGE: &str = "usage: aoc submit --day <n> --part 1|2 [--input <path>|-]";
+
+fn parse_args(args: &[String]) -> Result<(u32, Part, Option<String>), String> {
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        let value = it.next().ok_or(format!("{} needs a value", arg))?;
+        match arg.as_str() {
+            "--day" => {
+                day = Some(
+                    value
+                        .parse::<u32>()
+                        .map_err(|_| format!("invalid day {}", value))?,
+                )
+            }
+            "--part" => {
+                part = match value.as_str() {
+                    "1" => Some(Part::One),
+                    "2" => Some(Part::Two),
+                    _ => return Err(format!("--part must be 1 or 2, got {}", value)),
+                }
+            }
+            "--input" => input = Some(value.clone()),
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+    match (day, part) {
+        (Some(day), Some(part)) => Ok((day, part, input)),
+        _ => Err(String::from("--day and --part are required")),
+    }
+}
+
+/// `aoc submit`: solve one part and send the answer, unless the guess history for the day
+/// already shows it can't be right.
+pub fn main(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (day, part, input) = parse_args(args).map_err(|e| format!("{}\n{}", e, USAGE))?;
+    let solution = registry::get(day).ok_or(format!("day {} is not implemented", day))?;
+    let content = InputSource::for_day(day, input.as_deref()).read()?;
+    let answer = solution.parse(&content)?.part(part)?.to_string();
+    println!("day {:02} part {}: {}", day, part, answer);
+
+    // guesses belong with the puzzle, so they live beside the usual input whatever was solved
+    let history_path = match InputSource::for_day(day, None) {
+        InputSource::File(path) => History::path_for(&path),
+        InputSource::Stdin => unreachable!("inputs are never read from stdin by default"),
+    };
+    let mut history = History::load(&history_path)?;
+    if let Some(reason) = history.refuse(part, &answer) {
+        Err(format!("not submitting: {}", reason))?;
+    }
+
+    let verdict = Client::new(&Config::load()?)?.submit(day, part, &answer)?;
+    println!("{}", verdict);
+    history.record(Guess {
+        part,
+        answer,
+        verdict,
+    })
+}
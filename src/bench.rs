@@ -0,0 +1,226 @@
+use crate::solution::{DynSolution, Part, Unsolved};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Spread of timings for one phase, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "need at least one sample");
+        let mut ns: Vec<u64> = samples.iter().map(|d| d.as_nanos() as u64).collect();
+        ns.sort_unstable();
+        Stats {
+            min_ns: ns[0],
+            median_ns: ns[ns.len() / 2],
+            max_ns: ns[ns.len() - 1],
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ms = |ns: u64| ns as f64 / 1e6;
+        write!(
+            f,
+            "{:>10.3} {:>10.3} {:>10.3}",
+            ms(self.min_ns),
+            ms(self.median_ns),
+            ms(self.max_ns)
+        )
+    }
+}
+
+/// Timings for one day. A part is `None` if it isn't solved yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayTimings {
+    pub day: u32,
+    pub parse: Stats,
+    pub part1: Option<Stats>,
+    pub part2: Option<Stats>,
+}
+
+impl DayTimings {
+    pub fn phases(&self) -> [(&'static str, Option<Stats>); 3] {
+        [
+            ("parse", Some(self.parse)),
+            ("part1", self.part1),
+            ("part2", self.part2),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub iterations: usize,
+    pub days: Vec<DayTimings>,
+}
+
+impl Report {
+    pub fn load(path: &Path) -> Result<Report, Box<dyn Error>> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("could not read {}: {}", path.display(), err))?;
+        let report = serde_json::from_str(&content)
+            .map_err(|err| format!("could not parse {}: {}", path.display(), err))?;
+        Ok(report)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content + "\n")
+            .map_err(|err| format!("could not write {}: {}", path.display(), err))?;
+        Ok(())
+    }
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Time parsing and each part separately, `iterations` times over. Every part is timed against a
+/// freshly parsed input so that nothing cached by an earlier part is shared between runs.
+pub fn bench(
+    solution: &dyn DynSolution,
+    content: &str,
+    iterations: usize,
+) -> Result<DayTimings, Box<dyn Error>> {
+    let mut parse = vec![];
+    let mut parts: [Option<Vec<Duration>>; 2] = [Some(vec![]), Some(vec![])];
+    for _ in 0..iterations.max(1) {
+        let (parsed, elapsed) = time(|| solution.parse(content));
+        let parsed = parsed?;
+        parse.push(elapsed);
+
+        for (slot, part) in parts.iter_mut().zip(Part::BOTH) {
+            let Some(samples) = slot else {
+                continue;
+            };
+            match time(|| parsed.part(part)) {
+                (Ok(_), elapsed) => samples.push(elapsed),
+                (Err(err), _) if err.is::<Unsolved>() => *slot = None,
+                (Err(err), _) => return Err(format!("part {}: {}", part, err))?,
+            }
+        }
+    }
+
+    let [part1, part2] = parts.map(|samples| samples.map(|s| Stats::from_samples(&s)));
+    Ok(DayTimings {
+        day: solution.day(),
+        parse: Stats::from_samples(&parse),
+        part1,
+        part2,
+    })
+}
+
+/// A phase whose median got slower than the baseline's by more than the allowed fraction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regression {
+    pub day: u32,
+    pub phase: &'static str,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let change = (self.current_ns as f64 / self.baseline_ns as f64 - 1.0) * 100.0;
+        write!(
+            f,
+            "day {:02} {}: median {:.3}ms -> {:.3}ms (+{:.0}%)",
+            self.day,
+            self.phase,
+            self.baseline_ns as f64 / 1e6,
+            self.current_ns as f64 / 1e6,
+            change
+        )
+    }
+}
+
+/// Compare medians phase by phase. `threshold` is the fraction a median may grow by before it
+/// counts, e.g. `0.1` for 10%. Days and phases missing from either report are skipped.
+pub fn regressions(baseline: &Report, current: &Report, threshold: f64) -> Vec<Regression> {
+    let mut found = vec![];
+    for day in &current.days {
+        let Some(before) = baseline.days.iter().find(|d| d.day == day.day) else {
+            continue;
+        };
+        for ((phase, now), (_, then)) in day.phases().into_iter().zip(before.phases()) {
+            if let (Some(now), Some(then)) = (now, then) {
+                if now.median_ns as f64 > then.median_ns as f64 * (1.0 + threshold) {
+                    found.push(Regression {
+                        day: day.day,
+                        phase,
+                        baseline_ns: then.median_ns,
+                        current_ns: now.median_ns,
+                    });
+                }
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(median_ns: u64) -> Stats {
+        Stats {
+            min_ns: median_ns,
+            median_ns,
+            max_ns: median_ns,
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let samples = [5, 1, 3, 9, 4].map(Duration::from_nanos);
+        assert_eq!(
+            Stats::from_samples(&samples),
+            Stats {
+                min_ns: 1,
+                median_ns: 4,
+                max_ns: 9
+            }
+        );
+    }
+
+    #[test]
+    fn test_regressions() {
+        let report = |parse, part1, part2| Report {
+            iterations: 1,
+            days: vec![DayTimings {
+                day: 3,
+                parse: stats(parse),
+                part1: Some(stats(part1)),
+                part2,
+            }],
+        };
+        let baseline = report(100, 100, Some(stats(100)));
+        let current = report(105, 200, None);
+        assert_eq!(
+            regressions(&baseline, &current, 0.1),
+            vec![Regression {
+                day: 3,
+                phase: "part1",
+                baseline_ns: 100,
+                current_ns: 200,
+            }]
+        );
+        assert!(regressions(&baseline, &current, 1.5).is_empty());
+
+        let json = serde_json::to_string(&current).unwrap();
+        assert_eq!(serde_json::from_str::<Report>(&json).unwrap(), current);
+    }
+}
@@ -0,0 +1,217 @@
+use crate::config::Config;
+use crate::solution::Part;
+use crate::submit::Verdict;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const YEAR: u32 = 2022;
+
+const USER_AGENT: &str = concat!("advent-of-code-2022/", env!("CARGO_PKG_VERSION"));
+
+/// Talks to the puzzle site as the user whose session token is configured.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> Result<Client, Box<dyn Error>> {
+        Ok(Client {
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            session: config.session()?.to_string(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        })
+    }
+
+    fn day_url(&self, day: u32) -> String {
+        format!("{}/{}/day/{}", self.base_url, YEAR, day)
+    }
+
+    /// Download `day`'s puzzle input.
+    pub fn input(&self, day: u32) -> Result<String, Box<dyn Error>> {
+        let url = format!("{}/input", self.day_url(day));
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(code, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                Err(format!("GET {} failed with {}: {}", url, code, body.trim()))?
+            }
+            Err(err) => Err(format!("GET {} failed: {}", url, err))?,
+        }
+    }
+
+    /// Send `answer` for one part of `day` and report what the site thought of it.
+    pub fn submit(&self, day: u32, part: Part, answer: &str) -> Result<Verdict, Box<dyn Error>> {
+        let url = format!("{}/answer", self.day_url(day));
+        let level = part.to_string();
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &level), ("answer", answer)]);
+        let body = match response {
+            Ok(response) => response.into_string()?,
+            Err(ureq::Error::Status(code, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                Err(format!(
+                    "POST {} failed with {}: {}",
+                    url,
+                    code,
+                    body.trim()
+                ))?
+            }
+            Err(err) => Err(format!("POST {} failed: {}", url, err))?,
+        };
+        Ok(Verdict::from_response(&body)?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already on disk, so nothing was downloaded.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Make sure `day`'s input is at `path`, downloading it only if it isn't there yet. Inputs never
+/// change, so a cached input is never fetched again; an empty file, like the placeholder `aoc new`
+/// leaves, doesn't count.
+pub fn fetch(config: &Config, day: u32, path: &Path) -> Result<Fetched, Box<dyn Error>> {
+    if fs::metadata(path).is_ok_and(|meta| meta.len() > 0) {
+        return Ok(Fetched::Cached(path.to_path_buf()));
+    }
+    let content = Client::new(config)?.input(day)?;
+
+    // write next to the destination and rename, so an interrupted fetch never leaves a partial
+    // input that looks cached
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("could not create {}: {}", dir.display(), err))?;
+    }
+    let partial = path.with_extension("partial");
+    fs::write(&partial, content)
+        .map_err(|err| format!("could not write {}: {}", partial.display(), err))?;
+    fs::rename(&partial, path)
+        .map_err(|err| format!("could not write {}: {}", path.display(), err))?;
+    Ok(Fetched::Downloaded(path.to_path_buf()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// A stand-in for the puzzle site that answers `responses.len()` requests in turn, each with
+    /// the given status and body, then hands back the requests it saw (request line, headers and
+    /// body).
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(n) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = n.trim().parse().unwrap();
+                    }
+                    request += &line;
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+                let mut content = vec![0; content_length];
+                std::io::Read::read_exact(&mut reader, &mut content).unwrap();
+                request += &String::from_utf8(content).unwrap();
+                requests.push(request);
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    fn config(base_url: &str) -> Config {
+        Config {
+            session: Some(String::from("s3cret")),
+            base_url: base_url.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_fetch_caches_input() {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let path = dir.join("d05").join("input");
+        let _ = fs::remove_dir_all(&dir);
+
+        let (url, server) = serve(vec![(200, "1\n2\n3\n")]);
+        let config = config(&url);
+        assert_eq!(
+            fetch(&config, 5, &path).unwrap(),
+            Fetched::Downloaded(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n3\n");
+
+        // the server only answers once, so this would fail if it asked again
+        assert_eq!(
+            fetch(&config, 5, &path).unwrap(),
+            Fetched::Cached(path.clone())
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2022/day/5/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=s3cret\r\n"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_submit() {
+        let page = "<article><p>That's not the right answer; your answer is too low.</p></article>";
+        let (url, server) = serve(vec![(200, page)]);
+        let client = Client::new(&config(&url)).unwrap();
+        assert_eq!(
+            client.submit(11, Part::Two, "12 34").unwrap(),
+            Verdict::TooLow
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2022/day/11/answer HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=s3cret\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=12+34"));
+    }
+
+    #[test]
+    fn test_fetch_error_status() {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-err-{}", std::process::id()));
+        let path = dir.join("d05").join("input");
+
+        let (url, server) = serve(vec![(400, "Puzzle inputs differ by user.")]);
+        let err = fetch(&config(&url), 5, &path).unwrap_err();
+        assert!(err.to_string().contains("failed with 400"));
+        assert!(!path.exists());
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,110 @@
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Overrides where the config file is read from.
+pub const CONFIG_VAR: &str = "AOC_CONFIG";
+/// The adventofcode.com `session` cookie, overriding the config file's.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Where the puzzle site lives, overriding the config file's. Point it at a local server to test.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Settings for talking to the puzzle site, read from a file of `key = value` lines (`session`
+/// and `base_url`; `#` starts a comment) with environment variables taking precedence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            session: None,
+            base_url: String::from(DEFAULT_BASE_URL),
+        }
+    }
+}
+
+impl Config {
+    /// `$AOC_CONFIG`, or `~/.config/aoc/config`.
+    pub fn default_path() -> Option<PathBuf> {
+        match env::var_os(CONFIG_VAR) {
+            Some(path) => Some(PathBuf::from(path)),
+            None => env::var_os("HOME")
+                .map(|home| Path::new(&home).join(".config").join("aoc").join("config")),
+        }
+    }
+
+    /// The config file if there is one, then the environment on top.
+    pub fn load() -> Result<Config, Box<dyn Error>> {
+        let mut config = match Config::default_path() {
+            Some(path) => match fs::read_to_string(&path) {
+                Ok(content) => {
+                    Config::parse(&content).map_err(|err| format!("{}: {}", path.display(), err))?
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => Config::default(),
+                Err(err) => Err(format!("could not read {}: {}", path.display(), err))?,
+            },
+            None => Config::default(),
+        };
+        if let Ok(session) = env::var(SESSION_VAR) {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var(BASE_URL_VAR) {
+            config.base_url = base_url;
+        }
+        Ok(config)
+    }
+
+    pub fn parse(content: &str) -> Result<Config, Box<dyn Error>> {
+        let mut config = Config::default();
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(format!("line {}: expected key = value", i + 1))?;
+            let value = value.trim().to_string();
+            match key.trim() {
+                "session" => config.session = Some(value),
+                "base_url" => config.base_url = value,
+                key => Err(format!("line {}: unknown key {:?}", i + 1, key))?,
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn session(&self) -> Result<&str, String> {
+        self.session.as_deref().ok_or(format!(
+            "no session token: set {} or add `session = <token>` to the config file",
+            SESSION_VAR
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config =
+            Config::parse("# mine\nsession = abc123\n\nbase_url=http://localhost:8080\n").unwrap();
+        assert_eq!(
+            config,
+            Config {
+                session: Some(String::from("abc123")),
+                base_url: String::from("http://localhost:8080"),
+            }
+        );
+        assert!(Config::parse("token = abc").is_err());
+        assert!(Config::default().session().is_err());
+    }
+}
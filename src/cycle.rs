@@ -0,0 +1,172 @@
+//! Finding where a repeated `step` starts going round in circles, so step N of a long simulation
+//! can be worked out from the first lap.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// States repeat from step `start` on, every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step whose state is the same as step `n`'s.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// A metric at step `n`, given its value at each step up to at least `start + length`, for
+    /// metrics that change by the same amount every lap, like a running total. Metrics that only
+    /// depend on the state can just be read at `reduce(n)` instead.
+    pub fn extrapolate(&self, values: &[i64], n: usize) -> i64 {
+        if n < self.start {
+            return values[n];
+        }
+        let per_lap = values[self.start + self.length] - values[self.start];
+        let laps = ((n - self.start) / self.length) as i64;
+        values[self.reduce(n)] + laps * per_lap
+    }
+}
+
+/// Run `step` from `initial` until a state repeats, remembering every state. Returns the cycle and
+/// the states from step 0 to `start + length`, where the first repeat appears.
+pub fn find_cycle<S, F>(initial: S, step: F) -> (Cycle, Vec<S>)
+where
+    S: Clone + Hash + Eq,
+    F: FnMut(&S) -> S,
+{
+    find_cycle_by_key(initial, step, S::clone)
+}
+
+/// Like `find_cycle`, but two states count as the same when their `key`s are equal. This lets a
+/// state carry things that don't repeat, like a running total, next to the part that does.
+pub fn find_cycle_by_key<S, K, F, G>(initial: S, mut step: F, mut key: G) -> (Cycle, Vec<S>)
+where
+    K: Hash + Eq,
+    F: FnMut(&S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut states = vec![initial];
+    loop {
+        let n = states.len() - 1;
+        let state = &states[n];
+        let state_key = key(state);
+        if let Some(&start) = seen.get(&state_key) {
+            let cycle = Cycle {
+                start,
+                length: n - start,
+            };
+            return (cycle, states);
+        }
+        seen.insert(state_key, n);
+        let next = step(state);
+        states.push(next);
+    }
+}
+
+/// Brent's algorithm: find the cycle keeping only two states at a time, at the cost of running
+/// `step` a few more times than `find_cycle` does. Suits states too big to remember them all.
+pub fn brent<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    // find the length: the hare runs ahead in laps of doubling length, the tortoise waiting at
+    // the start of each, until the hare lands on it
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // find the start: with the hare a whole cycle ahead, they meet where the cycle begins
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// The state after `n` steps from `initial`, simulating only until the first repeat.
+pub fn nth_state<S, F>(initial: S, step: F, n: usize) -> S
+where
+    S: Clone + Hash + Eq,
+    F: FnMut(&S) -> S,
+{
+    let (cycle, mut states) = find_cycle(initial, step);
+    states.swap_remove(cycle.reduce(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 3 steps to reach 4, then 4 -> 5 -> 6 -> 7 -> 8 -> 4 forever
+    fn step(&x: &u32) -> u32 {
+        if x < 8 {
+            x + 1
+        } else {
+            4
+        }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let expected = Cycle {
+            start: 3,
+            length: 5,
+        };
+        let (cycle, states) = find_cycle(1, step);
+        assert_eq!(cycle, expected);
+        assert_eq!(states, vec![1, 2, 3, 4, 5, 6, 7, 8, 4]);
+        assert_eq!(brent(1, step), expected);
+        assert_eq!(
+            brent(4, step),
+            Cycle {
+                start: 0,
+                ..expected
+            }
+        );
+
+        assert_eq!(nth_state(1, step, 2), 3);
+        assert_eq!(nth_state(1, step, 8), 4);
+        assert_eq!(nth_state(1, step, 1_000_000_000), 6);
+    }
+
+    #[test]
+    fn test_extrapolate_running_total() {
+        // the state is (value, total of values so far), but only the value repeats
+        let (cycle, states) = find_cycle_by_key(
+            (1, 0),
+            |&(x, total)| (step(&x), total + x as i64),
+            |&(x, _)| x,
+        );
+        let totals: Vec<i64> = states.iter().map(|&(_, total)| total).collect();
+        let mut state = (1, 0);
+        for n in 0..100 {
+            assert_eq!(cycle.extrapolate(&totals, n), state.1);
+            state = (step(&state.0), state.1 + state.0 as i64);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::error::AocError;
+use crate::params::{Param, Params};
+use crate::parse::Span;
+use crate::solution::{Explained, Part, Solution, Witness};
+use itertools::Itertools;
+use std::error::Error;
+
+const PARAMS: &[Param] = &[Param::new(
+    "top",
+    "3",
+    "elves whose calories part 2 adds up",
+)];
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u32 = 1;
+    const PARAMS: &'static [Param] = PARAMS;
+
+    /// Total calories carried by each elf, in input order.
+    type Input = Vec<i32>;
+    type Answer1 = Explained<i32>;
+    type Answer2 = Explained<i32>;
+
+    fn parse(contents: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let calories = Span::new(contents)
+            .paragraphs()
+            .map(|elf| Ok(elf.lines_as::<i32>()?.into_iter().sum()))
+            .collect::<Result<Vec<i32>, AocError>>()?;
+        Ok(calories)
+    }
+
+    /// Elves are numbered from 1 in the witness, in input order.
+    fn part1(calories: &Self::Input, _: &Params) -> Result<Explained<i32>, Box<dyn Error>> {
+        let (elf, &max) = calories
+            .iter()
+            .enumerate()
+            .max_by_key(|&(_, &n)| n)
+            .ok_or("no elves")?;
+        Ok(Explained::new(max, Witness::new().with("elf", elf + 1)))
+    }
+
+    fn part2(calories: &Self::Input, params: &Params) -> Result<Explained<i32>, Box<dyn Error>> {
+        let top: usize = params.get("top", Part::Two)?;
+        let top_elves = (1..)
+            .zip(calories.iter().copied())
+            .sorted_by_key(|&(_, n)| -n)
+            .take(top)
+            .collect::<Vec<(usize, i32)>>();
+        let total = top_elves.iter().map(|&(_, n)| n).sum();
+        let witness = Witness::new()
+            .with(
+                "elves",
+                top_elves.iter().map(|&(elf, _)| elf).collect::<Vec<_>>(),
+            )
+            .with(
+                "calories",
+                top_elves.iter().map(|&(_, n)| n).collect::<Vec<_>>(),
+            );
+        Ok(Explained::new(total, witness))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(Day01, "24000", "45000");
+}
@@ -0,0 +1,165 @@
+use crate::error::AocError;
+use crate::params::Params;
+use crate::solution::Solution;
+use std::collections::HashMap;
+use std::error::Error;
+
+#[derive(Clone, Copy)]
+enum Outcome {
+    Win = 0,
+    Loss = 1,
+    Draw = 2,
+}
+impl Outcome {
+    fn from_str(ch: &str) -> Option<Self> {
+        let map: HashMap<&str, Self> = vec![("X", Self::Loss), ("Y", Self::Draw), ("Z", Self::Win)]
+            .into_iter()
+            .collect::<HashMap<&str, Self>>();
+        return map.get(ch).copied();
+    }
+
+    fn score(&self) -> i32 {
+        match self {
+            Self::Win => 6,
+            Self::Draw => 3,
+            Self::Loss => 0,
+        }
+    }
+}
+
+// TODO: in fasterthanlime's version does he derive PartialEq and Eq?
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum RPS {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl RPS {
+    pub fn from_str(s: &str) -> Option<Self> {
+        // let map: HashMap<&str, Self> = vec![
+        //     ("A", Self::Rock),
+        //     ("B", Self::Paper),
+        //     ("C", Self::Scissors),
+        //     ("X", Self::Rock),
+        //     ("Y", Self::Paper),
+        //     ("Z", Self::Scissors),
+        // ]
+        // .into_iter()
+        // .collect();
+        // return map.get(s).map(|x| *x);
+
+        match s {
+            "A" => Some(Self::Rock),
+            "B" => Some(Self::Paper),
+            "C" => Some(Self::Scissors),
+            "X" => Some(Self::Rock),
+            "Y" => Some(Self::Paper),
+            "Z" => Some(Self::Scissors),
+            _ => None,
+        }
+    }
+
+    pub fn score(&self) -> i32 {
+        // let map: HashMap<Self, i32> = vec![(Self::Rock, 1), (Self::Paper, 2), (Self::Scissors, 3)]
+        //     .into_iter()
+        //     .collect();
+        // return *map.get(self).unwrap();
+
+        match self {
+            Self::Rock => 1,
+            Self::Paper => 2,
+            Self::Scissors => 3,
+        }
+    }
+
+    pub fn beats(&self, other: RPS) -> bool {
+        matches!(
+            (self, other),
+            (RPS::Rock, RPS::Scissors) | (RPS::Scissors, RPS::Paper) | (RPS::Paper, RPS::Rock)
+        )
+    }
+
+    pub fn outcome_score(&self, other: RPS) -> i32 {
+        if *self == other {
+            return 3;
+        } else if self.beats(other) {
+            return 6;
+        } else {
+            return 0;
+        }
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+
+    /// The two columns of the strategy guide, one pair per round.
+    type Input = Vec<(String, String)>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        input
+            .lines()
+            .map(|line| {
+                // let [mine, theirs] = line.split(' ').collect::<Vec<&str>>();
+                let pieces = line.split(' ').collect::<Vec<&str>>();
+                if pieces.len() != 2 {
+                    return Err(AocError::within(input, line, "expected two columns"));
+                }
+                if !matches!(pieces[0], "A" | "B" | "C") {
+                    return Err(AocError::within(input, pieces[0], "expected A, B or C"));
+                }
+                if !matches!(pieces[1], "X" | "Y" | "Z") {
+                    return Err(AocError::within(input, pieces[1], "expected X, Y or Z"));
+                }
+                Ok((pieces[0].to_string(), pieces[1].to_string()))
+            })
+            .collect::<Result<Self::Input, AocError>>()
+            .map_err(Box::from)
+    }
+
+    fn part1(rounds: &Self::Input, _: &Params) -> Result<i32, Box<dyn Error>> {
+        let total: i32 = rounds
+            .iter()
+            .map(|(theirs, mine)| {
+                let theirs = RPS::from_str(theirs).unwrap();
+                let mine = RPS::from_str(mine).unwrap();
+                // TODO: can i pattern match this?
+                mine.score() + mine.outcome_score(theirs)
+            })
+            .sum();
+        Ok(total)
+    }
+
+    fn part2(rounds: &Self::Input, _: &Params) -> Result<i32, Box<dyn Error>> {
+        let total = rounds
+            .iter()
+            .map(|(opponent, outcome)| {
+                use Outcome::*;
+                use RPS::*;
+                let opponent = RPS::from_str(opponent).unwrap();
+                let outcome = Outcome::from_str(outcome).unwrap();
+                let mine = match (opponent, outcome) {
+                    (Rock, Draw) | (Scissors, Win) | (Paper, Loss) => Rock,
+                    (Paper, Draw) | (Rock, Win) | (Scissors, Loss) => Paper,
+                    (Scissors, Draw) | (Paper, Win) | (Rock, Loss) => Scissors,
+                };
+                return mine.score() + outcome.score();
+            })
+            .sum::<i32>();
+
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(Day02, "15", "12");
+}
@@ -0,0 +1,130 @@
+use crate::error::AocError;
+use crate::params::Params;
+use crate::solution::Solution;
+use crate::trace;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+fn priority(ch: char) -> Option<i32> {
+    let chars = ('a'..='z').chain('A'..='Z');
+    let priorities = 1..=52;
+    let map: HashMap<char, i32> = chars.zip(priorities).collect();
+    return map.get(&ch).copied();
+}
+
+/// The one item in `shared` and its priority. `what` says whose items they are, for the error
+/// when there isn't exactly one.
+fn only_item(shared: HashSet<char>, what: &str) -> Result<(char, i32), String> {
+    match shared.into_iter().collect::<Vec<char>>()[..] {
+        [item] => priority(item)
+            .map(|priority| (item, priority))
+            .ok_or_else(|| format!("{} share {:?}, which isn't a letter", what, item)),
+        ref items => Err(format!(
+            "{} share {} items, expected exactly 1",
+            what,
+            items.len()
+        )),
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+
+    /// One rucksack per line.
+    type Input = Vec<String>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        for line in input.lines() {
+            if line.len() % 2 != 0 {
+                return Err(AocError::within(
+                    input,
+                    line,
+                    "rucksack has an odd number of items",
+                ))?;
+            }
+            if let Some((i, ch)) = line.char_indices().find(|&(_, ch)| priority(ch).is_none()) {
+                let item = &line[i..i + ch.len_utf8()];
+                return Err(AocError::within(input, item, "items must be letters"))?;
+            }
+        }
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(rucksacks: &Self::Input, _: &Params) -> Result<i32, Box<dyn Error>> {
+        let mut result = 0;
+        for (i, line) in rucksacks.iter().enumerate() {
+            let (head, tail) = line.split_at(line.len() / 2);
+            // TODO: could i use HashSet::from here?
+            let head_set: HashSet<char> = head.chars().collect();
+            let tail_set: HashSet<char> = tail.chars().collect();
+            let shared = head_set.intersection(&tail_set).copied().collect();
+            let what = format!("rucksack {}'s compartments", i + 1);
+            let (item, priority) = only_item(shared, &what)?;
+            trace::trace!("misplaced item", item = item, priority = priority);
+            result += priority;
+        }
+        Ok(result)
+    }
+
+    fn part2(rucksacks: &Self::Input, _: &Params) -> Result<i32, Box<dyn Error>> {
+        if rucksacks.len() % 3 != 0 {
+            let message = format!(
+                "expected the rucksacks in groups of 3, but there are {}",
+                rucksacks.len()
+            );
+            Err(message)?;
+        }
+        let mut result = 0;
+        for (i, chunk) in rucksacks.iter().chunks(3).into_iter().enumerate() {
+            let sets: Vec<HashSet<char>> =
+                chunk // chunk is iterator over Strings.
+                    .map(|line| line.chars().collect::<HashSet<char>>())
+                    .collect();
+            let shared = sets
+                .into_iter()
+                .reduce(|a, b| a.intersection(&b).copied().collect::<HashSet<char>>())
+                .unwrap_or_default();
+            let (item, priority) = only_item(shared, &format!("group {}'s rucksacks", i + 1))?;
+            trace::trace!("badge", item = item, priority = priority);
+            result += priority;
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(Day03, "157", "70");
+
+    #[test]
+    fn test_no_single_shared_item() {
+        let rucksacks = Day03::parse("abcd\n").unwrap();
+        assert_eq!(
+            Day03::part1(&rucksacks, &Params::default())
+                .unwrap_err()
+                .to_string(),
+            "rucksack 1's compartments share 0 items, expected exactly 1"
+        );
+        assert_eq!(
+            Day03::part2(&rucksacks, &Params::default())
+                .unwrap_err()
+                .to_string(),
+            "expected the rucksacks in groups of 3, but there are 1"
+        );
+
+        let rucksacks = Day03::parse("abab\nabab\nabab\n").unwrap();
+        assert_eq!(
+            Day03::part2(&rucksacks, &Params::default())
+                .unwrap_err()
+                .to_string(),
+            "group 1's rucksacks share 2 items, expected exactly 1"
+        );
+    }
+}
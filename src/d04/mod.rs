@@ -0,0 +1,80 @@
+use crate::error::AocError;
+use crate::params::Params;
+use crate::parse::Span;
+use crate::solution::Solution;
+use crate::trace;
+use std::error::Error;
+
+#[derive(Debug)]
+pub struct Range {
+    pub low: usize,
+    pub high: usize,
+}
+
+impl Range {
+    fn parse(span: Span) -> Result<Self, AocError> {
+        let (low, high) = span.split_pair::<usize, usize>("-")?;
+        return Ok(Range { low, high });
+    }
+
+    pub fn contains(&self, other: &Self) -> bool {
+        return self.low <= other.low && self.high >= other.high;
+    }
+
+    pub fn overlap(&self, other: &Self) -> bool {
+        return !(self.high < other.low || self.low > other.high);
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u32 = 4;
+
+    /// The pair of section ranges assigned on each line.
+    type Input = Vec<(Range, Range)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(content: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let pairs = Span::new(content)
+            .lines()
+            .map(|line| {
+                let [left, right] = line.split_n::<2>(",")?;
+                Ok((Range::parse(left)?, Range::parse(right)?))
+            })
+            .collect::<Result<Self::Input, AocError>>()?;
+        Ok(pairs)
+    }
+
+    fn part1(pairs: &Self::Input, _: &Params) -> Result<usize, Box<dyn Error>> {
+        let num = pairs
+            .iter()
+            .filter(|(left, right)| {
+                let contains = left.contains(right) || right.contains(left);
+                trace::trace!("pair", left = left, right = right, contains = contains);
+                contains
+            })
+            .count();
+        Ok(num)
+    }
+
+    fn part2(pairs: &Self::Input, _: &Params) -> Result<usize, Box<dyn Error>> {
+        let num = pairs
+            .iter()
+            .filter(|(left, right)| {
+                let overlap = left.overlap(right);
+                trace::trace!("pair", left = left, right = right, overlap = overlap);
+                overlap
+            })
+            .count();
+        Ok(num)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(Day04, "2", "4");
+}
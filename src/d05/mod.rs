@@ -0,0 +1,281 @@
+use crate::animate::{Animator, Colour};
+use crate::error::AocError;
+use crate::params::Params;
+use crate::parse::Span;
+use crate::simulation::{Driver, Simulation};
+use crate::solution::{Part, Solution};
+use crate::template::Template;
+use itertools::Itertools;
+use std::error::Error;
+use std::fmt;
+use std::sync::LazyLock;
+
+pub type Stack = Vec<char>;
+
+#[derive(Debug, Clone, Default)]
+pub struct BoardState {
+    pub stacks: Vec<Stack>,
+}
+impl BoardState {
+    // TODO: why do i need the lifetime parameter here? how could the &str have a lifetime less
+    // than from_lines?
+    fn from_lines<'a>(lines: impl Iterator<Item = &'a str>) -> Self {
+        let grid: Vec<Vec<char>> = lines.map(|line| line.chars().collect::<Stack>()).collect();
+        let nrows = grid.len();
+        let last_line = grid.last().unwrap();
+        let col_indicies =
+            last_line
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, ch)| if *ch == ' ' { None } else { Some(idx) });
+        let stacks = col_indicies
+            .map(|col_idx| {
+                let row_indicies = (0..nrows - 1).rev().take_while(|&row_idx| {
+                    col_idx < grid[row_idx].len() && grid[row_idx][col_idx] != ' '
+                });
+                row_indicies
+                    .map(|row_idx| grid[row_idx][col_idx])
+                    .collect_vec()
+            })
+            .collect_vec();
+        return BoardState { stacks };
+    }
+}
+
+/// Drawn the way the input draws it, crates stacked up from the numbered bottom line.
+impl fmt::Display for BoardState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        for row in (0..height).rev() {
+            let line = self
+                .stacks
+                .iter()
+                .map(|stack| match stack.get(row) {
+                    Some(ch) => format!("[{}]", ch),
+                    None => String::from("   "),
+                })
+                .join(" ");
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        let numbers = (1..=self.stacks.len())
+            .map(|n| format!("{:^3}", n))
+            .join(" ");
+        write!(f, "{}", numbers.trim_end())
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Command {
+    pub num: usize,
+    pub from: usize,
+    pub to: usize,
+}
+static COMMAND: LazyLock<Template> =
+    LazyLock::new(|| Template::new("move {num} from {from} to {to}"));
+
+impl Command {
+    fn parse(line: Span) -> Result<Command, AocError> {
+        let fields = COMMAND.matches(line)?;
+        let stack = |name: &str| match fields.get::<usize>(name)? {
+            0 => Err(fields.span(name).error("stacks are numbered from 1")),
+            n => Ok(n - 1),
+        };
+        Ok(Command {
+            num: fields.get("num")?,
+            from: stack("from")?,
+            to: stack("to")?,
+        })
+    }
+}
+
+/// Move crates one at a time. Panics if `from` holds fewer than `num` crates, which `parse` rules
+/// out for its procedures.
+pub fn execute_command_flip_order(mut boardstate: BoardState, command: Command) -> BoardState {
+    let Command { num, from, to } = command;
+    for _ in 1..=num {
+        let x = boardstate.stacks[from].pop().unwrap();
+        boardstate.stacks[to].push(x);
+    }
+
+    return boardstate;
+}
+
+/// Move crates all at once. Panics like `execute_command_flip_order`.
+pub fn execute_command_maintain_order(mut boardstate: BoardState, command: Command) -> BoardState {
+    let Command { num, from, to } = command;
+    let from_stack_len = boardstate.stacks[from].len();
+    let chunk = boardstate.stacks[from]
+        .splice((from_stack_len - num)..from_stack_len, vec![])
+        .collect_vec();
+    boardstate.stacks[to].extend(chunk);
+
+    return boardstate;
+}
+
+/// The starting stacks and the rearrangement procedure.
+#[derive(Debug, Clone)]
+pub struct Procedure {
+    pub boardstate: BoardState,
+    pub commands: Vec<Command>,
+}
+
+impl Procedure {
+    pub fn crane(&self, execute: fn(BoardState, Command) -> BoardState) -> Crane<'_> {
+        Crane {
+            boardstate: self.boardstate.clone(),
+            commands: &self.commands,
+            next: 0,
+            execute,
+        }
+    }
+
+    pub fn top_crates(
+        &self,
+        execute: fn(BoardState, Command) -> BoardState,
+    ) -> Result<String, Box<dyn Error>> {
+        let mut driver = Driver::new(self.crane(execute));
+        driver.run_to_end()?;
+        let tops = driver
+            .sim()
+            .boardstate
+            .stacks
+            .iter()
+            .enumerate()
+            .map(|(i, stack)| {
+                stack
+                    .last()
+                    .ok_or_else(|| format!("stack {} ends up empty", i + 1))
+            })
+            .collect::<Result<String, String>>()?;
+        Ok(tops)
+    }
+}
+
+/// The crane working through the procedure one command at a time.
+pub struct Crane<'a> {
+    pub boardstate: BoardState,
+    pub commands: &'a [Command],
+    pub next: usize,
+    pub execute: fn(BoardState, Command) -> BoardState,
+}
+
+impl Simulation for Crane<'_> {
+    type Snapshot = BoardState;
+
+    fn step(&mut self) {
+        let boardstate = std::mem::take(&mut self.boardstate);
+        self.boardstate = (self.execute)(boardstate, self.commands[self.next]);
+        self.next += 1;
+    }
+
+    fn is_done(&self) -> bool {
+        self.next == self.commands.len()
+    }
+
+    fn snapshot(&self) -> BoardState {
+        self.boardstate.clone()
+    }
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+
+    type Input = Procedure;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(content: &str) -> Result<Self::Input, Box<dyn Error>> {
+        // the drawing, then the procedure
+        let mut paragraphs = Span::new(content).paragraphs();
+        let drawing = paragraphs.next().ok_or_else(|| {
+            AocError::at_offset(content, 0, 0, "expected a drawing of the stacks")
+        })?;
+        let boardstate = BoardState::from_lines(drawing.as_str().lines());
+        let nstacks = boardstate.stacks.len();
+        // both cranes move the same number of crates, so the heights tell whether a move can
+        // take as many as it asks for
+        let mut heights = boardstate.stacks.iter().map(Vec::len).collect_vec();
+        let commands = paragraphs
+            .flat_map(|paragraph| paragraph.lines())
+            .map(|line| {
+                let command = Command::parse(line)?;
+                if command.from >= nstacks || command.to >= nstacks {
+                    return Err(line.error(format!("there are only {} stacks", nstacks)));
+                }
+                let Command { num, from, to } = command;
+                if num > heights[from] {
+                    let message = format!("stack {} only has {} crates", from + 1, heights[from]);
+                    return Err(line.error(message));
+                }
+                heights[from] -= num;
+                heights[to] += num;
+                Ok(command)
+            })
+            .collect::<Result<Vec<Command>, AocError>>()?;
+        Ok(Procedure {
+            boardstate,
+            commands,
+        })
+    }
+
+    fn part1(procedure: &Self::Input, _: &Params) -> Result<String, Box<dyn Error>> {
+        procedure.top_crates(execute_command_flip_order)
+    }
+
+    fn part2(procedure: &Self::Input, _: &Params) -> Result<String, Box<dyn Error>> {
+        procedure.top_crates(execute_command_maintain_order)
+    }
+
+    fn animate(
+        procedure: &Self::Input,
+        _: &Params,
+        part: Part,
+        animator: &mut Animator,
+    ) -> Result<(), Box<dyn Error>> {
+        let execute = match part {
+            Part::One => execute_command_flip_order,
+            Part::Two => execute_command_maintain_order,
+        };
+        animator.set_palette(|ch| match ch {
+            'A'..='Z' => Some(Colour::Yellow),
+            '1'..='9' => Some(Colour::Cyan),
+            _ => None,
+        });
+        let crane = Driver::new(procedure.crane(execute));
+        animator.play_driver(crane, |crane| crane.boardstate.to_string())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(Day05, "CMZ", "MCD");
+
+    const DRAWING: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n";
+
+    #[test]
+    fn test_moving_too_many_crates() {
+        let content = format!("{}move 1 from 2 to 1\nmove 4 from 1 to 3\n", DRAWING);
+        let err = Day05::parse(&content).unwrap_err();
+        let err = err.downcast_ref::<AocError>().unwrap();
+        assert_eq!(
+            (err.line, err.message.as_str()),
+            (7, "stack 1 only has 3 crates")
+        );
+    }
+
+    #[test]
+    fn test_empty_stack_has_no_top() {
+        let procedure = Day05::parse(&format!("{}move 1 from 3 to 1\n", DRAWING)).unwrap();
+        assert_eq!(
+            Day05::part1(&procedure, &Params::default())
+                .unwrap_err()
+                .to_string(),
+            "stack 3 ends up empty"
+        );
+    }
+}
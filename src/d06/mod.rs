@@ -0,0 +1,81 @@
+use crate::error::AocError;
+use crate::params::{Param, Params};
+use crate::solution::{Part, Solution};
+use crate::trace;
+use std::collections::HashMap;
+use std::error::Error;
+
+fn num_unique(s: &str) -> usize {
+    let mut hist: HashMap<char, usize> = HashMap::new();
+    for c in s.chars() {
+        hist.insert(c, *hist.get(&c).unwrap_or(&0) + 1);
+    }
+    trace::trace!("window", window = s, counts = hist);
+    return hist.iter().filter(|(_, &n)| n == 1).count();
+}
+
+/// Number of characters read once the last `window_size` characters are all different.
+pub fn first_marker(content: &str, window_size: usize) -> Option<usize> {
+    (window_size..content.len()).find_map(|end| {
+        let start = end - window_size;
+        if num_unique(&content[start..end]) == window_size {
+            return Some(end);
+        }
+        return None;
+    })
+}
+
+const PARAMS: &[Param] = &[Param::per_part(
+    "window_size",
+    "4",
+    "14",
+    "different characters in a row that make a marker",
+)];
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u32 = 6;
+    const PARAMS: &'static [Param] = PARAMS;
+
+    /// The datastream buffer.
+    type Input = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(content: &str) -> Result<Self::Input, Box<dyn Error>> {
+        // markers are found by slicing bytes, so every character has to be one byte wide
+        if let Some((i, ch)) = content.char_indices().find(|(_, ch)| !ch.is_ascii()) {
+            let message = format!("unexpected character {:?}", ch);
+            return Err(AocError::at_offset(content, i, ch.len_utf8(), message))?;
+        }
+        Ok(content.to_string())
+    }
+
+    fn part1(content: &Self::Input, params: &Params) -> Result<usize, Box<dyn Error>> {
+        let window_size = params.get_at_least("window_size", Part::One, 1)?;
+        Ok(first_marker(content, window_size).ok_or("no marker found")?)
+    }
+
+    fn part2(content: &Self::Input, params: &Params) -> Result<usize, Box<dyn Error>> {
+        let window_size = params.get_at_least("window_size", Part::Two, 1)?;
+        Ok(first_marker(content, window_size).ok_or("no marker found")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(Day06, "7", "19");
+
+    #[test]
+    fn test_window_size_must_be_positive() {
+        let params = Params::set(PARAMS, &[String::from("window_size.1=0")]).unwrap();
+        let result = Day06::part1(&String::from("abcd"), &params);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "parameter window_size=0: must be at least 1"
+        );
+    }
+}
@@ -0,0 +1,351 @@
+use crate::dot::{Dot, ToDot};
+use crate::error::AocError;
+use crate::params::{Param, Params};
+use crate::parse::Span;
+use crate::solution::{Explained, Part, Solution, Witness};
+use crate::template::Template;
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+// use std::assert_matches::assert_matches;
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::LazyLock;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command<'a> {
+    Cd(&'a str),
+    Ls,
+}
+
+static CD: LazyLock<Template> = LazyLock::new(|| Template::new("$ cd {dir}"));
+static LS: LazyLock<Template> = LazyLock::new(|| Template::new("$ ls"));
+static DIR: LazyLock<Template> = LazyLock::new(|| Template::new("dir {name}"));
+static FILE: LazyLock<Template> = LazyLock::new(|| Template::new("{size} {name}"));
+
+impl<'a> Command<'a> {
+    fn parse(line: Span<'a>) -> Result<Self, AocError> {
+        match line.as_str().split(' ').nth(1) {
+            Some("cd") => return Ok(Self::Cd(CD.matches(line)?.str("dir"))),
+            Some("ls") => {
+                LS.matches(line)?;
+                return Ok(Self::Ls);
+            }
+            _ => return Err(line.error("expected \"$ cd <dir>\" or \"$ ls\"")),
+        }
+    }
+}
+
+// TODO: why do i need the lifetime parameter here?. Oh you always need it for enums and structs. Never elided
+// Ans: It's not that the str must outlive the filenode (although that is true), it's that it
+// refernces some other piece of text and that other text must outlive the filenode
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum FileNode<'a> {
+    Dir(&'a str),
+    File { size: usize, name: &'a str },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ParsedLine<'a> {
+    Command(Command<'a>),
+    FileNode(FileNode<'a>),
+}
+
+impl<'a> FileNode<'a> {
+    fn parse(line: Span<'a>) -> Result<Self, AocError> {
+        if line.as_str().starts_with("dir ") {
+            return Ok(FileNode::Dir(DIR.matches(line)?.str("name")));
+        }
+        let fields = FILE.matches(line)?;
+        Ok(FileNode::File {
+            size: fields.get("size")?,
+            name: fields.str("name"),
+        })
+    }
+}
+
+pub type NodeHandle = Rc<RefCell<Node>>;
+
+#[derive(Debug)]
+pub enum Node {
+    File {
+        name: String,
+        size: usize,
+        parent: Option<Weak<RefCell<Node>>>,
+    },
+    Dir {
+        name: String,
+        children: HashMap<String, NodeHandle>,
+        parent: Option<Weak<RefCell<Node>>>,
+    },
+}
+
+impl Node {
+    pub fn parent(&self) -> Option<NodeHandle> {
+        let p = match self {
+            Node::File { parent, .. } => parent,
+            Node::Dir { parent, .. } => parent,
+        };
+        return p.as_ref().and_then(Weak::upgrade);
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Node::File { name, .. } => name,
+            Node::Dir { name, .. } => name,
+        }
+    }
+
+    pub fn is_dir(&self) -> bool {
+        matches!(self, Node::Dir { .. })
+    }
+}
+
+/// The full path of the node, like `/a/e`.
+pub fn path(handle: &NodeHandle) -> String {
+    let mut names = vec![];
+    let mut node = Some(handle.clone());
+    while let Some(current) = node {
+        let current = current.borrow();
+        if current.parent().is_some() {
+            names.push(current.name().to_string());
+        }
+        node = current.parent();
+    }
+    names.reverse();
+    format!("/{}", names.join("/"))
+}
+
+fn put(parent: NodeHandle, fnode: FileNode) -> NodeHandle {
+    let newnode: NodeHandle = match fnode {
+        FileNode::Dir(name) => Rc::new(RefCell::new(Node::Dir {
+            name: name.to_string(),
+            children: HashMap::new(),
+            parent: Some(Rc::downgrade(&parent)),
+        })),
+        FileNode::File { size, name } => Rc::new(RefCell::new(Node::File {
+            name: name.to_string(),
+            size,
+            parent: Some(Rc::downgrade(&parent)),
+        })),
+    };
+
+    if let Node::Dir { children, .. } = &mut *parent.borrow_mut() {
+        match fnode {
+            FileNode::Dir(dirname) => {
+                children.insert(dirname.to_string(), newnode.clone());
+            }
+            FileNode::File { name, .. } => {
+                children.insert(name.to_string(), newnode.clone());
+            }
+        }
+    }
+    return newnode;
+}
+
+/// The directory tree rebuilt from the terminal session.
+#[derive(Debug)]
+pub struct FileSystem {
+    pub root: NodeHandle,
+}
+
+impl FileSystem {
+    /// Every node in the tree with its total size, parents before their children.
+    pub fn sizes(&self) -> Vec<(NodeHandle, usize)> {
+        dfs(self.root.clone())
+    }
+
+    /// Paths and sizes of every directory in the tree, root first.
+    pub fn dir_sizes(&self) -> Vec<(String, usize)> {
+        self.sizes()
+            .into_iter()
+            .filter(|(handle, _)| handle.borrow().is_dir())
+            .map(|(handle, size)| (path(&handle), size))
+            .collect()
+    }
+}
+
+/// Directories and files labelled with their total sizes, each with an edge from the directory
+/// holding it. Ids are full paths, and children go in name order so the output is the same from
+/// run to run.
+impl ToDot for FileSystem {
+    fn to_dot(&self) -> Dot {
+        let sizes = self
+            .sizes()
+            .into_iter()
+            .map(|(handle, size)| (Rc::as_ptr(&handle), size))
+            .collect::<HashMap<_, _>>();
+        let mut dot = Dot::new("d07");
+        add_to_dot(&mut dot, &self.root, "/", &sizes);
+        dot
+    }
+}
+
+fn add_to_dot(
+    dot: &mut Dot,
+    handle: &NodeHandle,
+    path: &str,
+    sizes: &HashMap<*const RefCell<Node>, usize>,
+) {
+    let node = handle.borrow();
+    let label = format!("{}\n{}", node.name(), sizes[&Rc::as_ptr(handle)]);
+    match &*node {
+        Node::Dir { children, .. } => {
+            dot.node(path, &[("label", label), ("shape", String::from("folder"))]);
+            let mut children = children.iter().collect::<Vec<_>>();
+            children.sort_by_key(|&(name, _)| name);
+            for (name, child) in children {
+                let child_path = format!("{}/{}", path.trim_end_matches('/'), name);
+                dot.edge(path, &child_path, &[]);
+                add_to_dot(dot, child, &child_path, sizes);
+            }
+        }
+        Node::File { .. } => dot.node(path, &[("label", label), ("shape", String::from("note"))]),
+    }
+}
+
+impl<'a> TryFrom<&'a str> for FileSystem {
+    type Error = AocError;
+
+    fn try_from(content: &'a str) -> Result<Self, Self::Error> {
+        let mut parsed_lines = Span::new(content)
+            .lines()
+            .map(|line| {
+                let parsed = if line.as_str().starts_with('$') {
+                    ParsedLine::Command(Command::parse(line)?)
+                } else {
+                    ParsedLine::FileNode(FileNode::parse(line)?)
+                };
+                Ok((line, parsed))
+            })
+            .peekable();
+
+        let (first_line, first) = parsed_lines
+            .next()
+            .ok_or_else(|| AocError::at_end(content, "expected \"$ cd /\""))??;
+        if first != ParsedLine::Command(Command::Cd("/")) {
+            return Err(first_line.error("expected \"$ cd /\""));
+        }
+        let root = Rc::new(RefCell::new(Node::Dir {
+            name: String::from("/"),
+            children: HashMap::new(),
+            parent: None,
+        }));
+        let mut current_node = root.clone();
+
+        let it = &mut parsed_lines;
+        while let Some(_pl) = it.next() {
+            let (line, pl) = _pl?;
+            match pl {
+                ParsedLine::Command(Command::Cd("..")) => {
+                    let parent = current_node.borrow().parent();
+                    current_node = parent.ok_or_else(|| line.error("already at the root"))?;
+                }
+                ParsedLine::Command(Command::Cd(dirname)) => {
+                    current_node = put(current_node.clone(), FileNode::Dir(dirname));
+                }
+                ParsedLine::Command(Command::Ls) => {
+                    while let Some(Ok((_, ParsedLine::FileNode(fnode)))) = it.peek() {
+                        // push fnode to State
+                        put(current_node.clone(), *fnode);
+                        it.next();
+                    }
+                }
+                ParsedLine::FileNode(_) => {
+                    let message = "got file node not preceeded by ls";
+                    return Err(line.error(message));
+                }
+            }
+        }
+
+        Ok(FileSystem { root })
+    }
+}
+
+const PARAMS: &[Param] = &[
+    Param::new("disk_size", "70000000", "size of the whole disk"),
+    Param::new(
+        "space_needed",
+        "30000000",
+        "free space part 2 must make for the update",
+    ),
+];
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+    const PARAMS: &'static [Param] = PARAMS;
+
+    type Input = FileSystem;
+    type Answer1 = Explained<usize>;
+    type Answer2 = Explained<usize>;
+
+    fn parse(content: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(FileSystem::try_from(content)?)
+    }
+
+    fn part1(fs: &Self::Input, _: &Params) -> Result<Explained<usize>, Box<dyn Error>> {
+        let mut small = fs
+            .dir_sizes()
+            .into_iter()
+            .filter(|&(_, size)| size <= 100000)
+            .collect::<Vec<_>>();
+        small.sort();
+        let total = small.iter().map(|&(_, size)| size).sum();
+        Ok(Explained::new(
+            total,
+            Witness::new().with("directories", small),
+        ))
+    }
+
+    fn part2(fs: &Self::Input, params: &Params) -> Result<Explained<usize>, Box<dyn Error>> {
+        let sizes = fs.dir_sizes();
+        let root_size = sizes[0].1;
+        let disk_size: usize = params.get_at_least("disk_size", Part::Two, 1)?;
+        let space_needed: usize = params.get("space_needed", Part::Two)?;
+        let unused_space = disk_size
+            .checked_sub(root_size)
+            .ok_or("the files don't fit on the disk")?;
+        let amt_to_free = space_needed.saturating_sub(unused_space);
+        let (directory, big_enough) = sizes
+            .into_iter()
+            .filter(|&(_, size)| size >= amt_to_free)
+            .min_by_key(|&(_, size)| size)
+            .ok_or("no directory is big enough")?;
+        let witness = Witness::new()
+            .with("directory", directory)
+            .with("to_free", amt_to_free);
+        Ok(Explained::new(big_enough, witness))
+    }
+
+    fn graph(fs: &Self::Input, _: &Params, _part: Part) -> Result<Dot, Box<dyn Error>> {
+        Ok(fs.to_dot())
+    }
+}
+
+fn dfs(r: NodeHandle) -> Vec<(NodeHandle, usize)> {
+    let mut results: Vec<(NodeHandle, usize)> = vec![];
+    let mut total: usize = 0;
+    match *r.borrow() {
+        Node::Dir { ref children, .. } => {
+            for child in children.values() {
+                let child_results = dfs(child.clone());
+                let (_, t) = &child_results[0];
+                total += t;
+                results.extend(child_results);
+            }
+            results.insert(0, (r.clone(), total));
+        }
+        Node::File { size, .. } => {
+            results.push((r.clone(), size));
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(Day07, "95437", "24933642");
+}
@@ -0,0 +1,141 @@
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::image::{Image, Rgb};
+use crate::params::Params;
+use crate::point::{Direction, Point2};
+use crate::solution::{Part, Solution};
+use crate::trace;
+use std::error::Error;
+
+/// Tree heights.
+pub type Forest = Grid<u8>;
+
+pub fn parse(content: &str) -> Result<Forest, AocError> {
+    Grid::parse(content, |c| match c.to_digit(10) {
+        Some(height) => Ok(height as u8),
+        None => Err(String::from("tree heights are digits")),
+    })
+}
+
+/// Whether the tree at `pos` can be seen from outside the forest, i.e. every tree between it and
+/// some edge is shorter.
+fn is_visible(forest: &Forest, pos: Point2, height: u8) -> bool {
+    Direction::ORTHOGONAL.iter().any(|&direction| {
+        forest
+            .walk(pos, direction)
+            .all(|(_, &other)| other < height)
+    })
+}
+
+fn count_visible(forest: &Forest) -> usize {
+    forest
+        .iter()
+        .filter(|&(pos, &height)| is_visible(forest, pos, height))
+        .count()
+}
+
+fn scenic_score(forest: &Forest, pos: Point2, height: u8) -> usize {
+    // Walk outwards until the edge or the first tree at least as tall, which is still visible.
+    Direction::ORTHOGONAL
+        .iter()
+        .map(|&direction| {
+            let mut seen = 0;
+            for (_, &other) in forest.walk(pos, direction) {
+                seen += 1;
+                if other >= height {
+                    break;
+                }
+            }
+            seen
+        })
+        .product()
+}
+
+fn best_scenic_score(forest: &Forest) -> usize {
+    let mut best = 0;
+    for (pos, &height) in forest.iter() {
+        let this = scenic_score(forest, pos, height);
+        if this > best {
+            trace::debug!("new best", row = pos.y, col = pos.x, score = this);
+            best = this;
+        }
+    }
+    best
+}
+
+/// Trees shaded by height, green where they can be seen from outside and grey where they can't.
+fn draw_visible(forest: &Forest) -> Image {
+    let shaded = Grid::from_cells(
+        forest.width(),
+        forest.height(),
+        forest
+            .iter()
+            .map(|(pos, &height)| (height, is_visible(forest, pos, height)))
+            .collect(),
+    );
+    Image::from_grid(&shaded, |&(height, visible)| {
+        let t = height as f64 / 9.0;
+        if visible {
+            Rgb(10, 60, 10).lerp(Rgb(120, 240, 80), t)
+        } else {
+            Rgb(30, 30, 30).lerp(Rgb::GREY, t)
+        }
+    })
+}
+
+/// Every tree's scenic score as a heatmap, with the best tree picked out in red.
+fn draw_scenic_scores(forest: &Forest) -> Image {
+    let scores = Grid::from_cells(
+        forest.width(),
+        forest.height(),
+        forest
+            .iter()
+            .map(|(pos, &height)| scenic_score(forest, pos, height))
+            .collect(),
+    );
+    let best = scores.iter().max_by_key(|&(_, &score)| score);
+    let top = best.map_or(0, |(_, &score)| score).max(1);
+    let mut image = Image::from_grid(&scores, |&score| {
+        // square root, since a handful of trees score far above the rest
+        Rgb::BLACK.lerp(Rgb::YELLOW, (score as f64 / top as f64).sqrt())
+    });
+    image.overlay(best.map(|(pos, _)| pos), Rgb::RED);
+    image
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u32 = 8;
+
+    type Input = Forest;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(content: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse(content)?)
+    }
+
+    fn part1(grid: &Self::Input, _: &Params) -> Result<usize, Box<dyn Error>> {
+        Ok(count_visible(grid))
+    }
+
+    fn part2(grid: &Self::Input, _: &Params) -> Result<usize, Box<dyn Error>> {
+        Ok(best_scenic_score(grid))
+    }
+
+    /// The visible trees for part 1, or a heatmap of scenic scores for part 2.
+    fn images(grid: &Self::Input, _: &Params, part: Part) -> Result<Vec<Image>, Box<dyn Error>> {
+        Ok(vec![match part {
+            Part::One => draw_visible(grid),
+            Part::Two => draw_scenic_scores(grid),
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(Day08, "21", "8");
+}
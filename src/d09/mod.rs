@@ -0,0 +1,233 @@
+use crate::animate::{Animator, Colour};
+use crate::error::AocError;
+use crate::params::{Param, Params};
+use crate::point::{Direction, Point2};
+use crate::simulation::{Driver, Simulation};
+use crate::solution::{Part, Solution};
+use crate::sparse_grid::SparseGrid;
+use crate::trace;
+use std::error::Error;
+
+/// Knot positions, head first.
+#[derive(Debug, Clone, Default)]
+pub struct Rope {
+    pub knots: Vec<Point2>,
+}
+
+impl Rope {
+    pub fn new(start: Point2, nknots: usize) -> Rope {
+        Rope {
+            knots: vec![start; nknots],
+        }
+    }
+
+    pub fn head(&self) -> Point2 {
+        self.knots[0]
+    }
+
+    pub fn tail(&self) -> Point2 {
+        *self.knots.last().unwrap()
+    }
+}
+
+pub fn move_rope(mut rope: Rope, dir: Direction) -> Rope {
+    rope.knots[0] += dir;
+    // each knot follows the one in front of it
+    for i in 1..rope.knots.len() {
+        let head = rope.knots[i - 1];
+        let tail = &mut rope.knots[i];
+        if tail.chebyshev(head) > 1 {
+            *tail = tail.step_toward(head);
+        }
+    }
+
+    return rope;
+}
+
+/// The rope drawn over the area it covers, with `H` for the head, `T` for the tail and the
+/// knots between numbered. Where knots overlap the one nearest the head is drawn.
+fn draw_rope(rope: &Rope) -> SparseGrid<char> {
+    let last = rope.knots.len() - 1;
+    rope.knots
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(i, &knot)| match i {
+            0 => (knot, 'H'),
+            _ if i == last => (knot, 'T'),
+            _ => (knot, char::from_digit(i as u32 % 10, 10).unwrap()),
+        })
+        .collect()
+}
+
+/// The head following its motions one step at a time, with the rest of the rope in tow.
+pub struct RopeWalk {
+    pub rope: Rope,
+    /// Every step the head takes, in order.
+    pub moves: Vec<Direction>,
+    pub next: usize,
+    /// Everywhere the tail has been.
+    pub visited: SparseGrid<char>,
+}
+
+impl RopeWalk {
+    pub fn new(rope: Rope, motions: &[(Direction, usize)]) -> RopeWalk {
+        let moves = motions
+            .iter()
+            .flat_map(|&(dir, n)| std::iter::repeat_n(dir, n))
+            .collect();
+        let mut visited = SparseGrid::new();
+        visited.insert(rope.tail(), '#');
+        RopeWalk {
+            rope,
+            moves,
+            next: 0,
+            visited,
+        }
+    }
+}
+
+impl Simulation for RopeWalk {
+    type Snapshot = SparseGrid<char>;
+
+    fn step(&mut self) {
+        let dir = self.moves[self.next];
+        trace::trace!("move", step = self.next, direction = dir);
+        self.rope = move_rope(std::mem::take(&mut self.rope), dir);
+        self.visited.insert(self.rope.tail(), '#');
+        self.next += 1;
+    }
+
+    fn is_done(&self) -> bool {
+        self.next == self.moves.len()
+    }
+
+    fn snapshot(&self) -> SparseGrid<char> {
+        draw_rope(&self.rope)
+    }
+}
+
+const PARAMS: &[Param] = &[
+    Param::per_part(
+        "knots",
+        "2",
+        "10",
+        "knots in the rope, head and tail included",
+    ),
+    Param::new("start", "5,5", "where every knot starts, as x,y"),
+];
+
+/// The part's rope, as set by `params`, about to follow `motions`.
+fn rope_walk(
+    motions: &[(Direction, usize)],
+    params: &Params,
+    part: Part,
+) -> Result<RopeWalk, Box<dyn Error>> {
+    let nknots: usize = params.get_at_least("knots", part, 1)?;
+    let start: Point2 = params.get("start", part)?;
+    let rope = Rope::new(start, nknots);
+    trace::debug!("starting rope", knots = nknots, rope = %draw_rope(&rope));
+    Ok(RopeWalk::new(rope, motions))
+}
+
+/// Number of distinct positions the tail of the part's rope visits.
+fn count_tail_positions(
+    motions: &[(Direction, usize)],
+    params: &Params,
+    part: Part,
+) -> Result<usize, Box<dyn Error>> {
+    let mut driver = Driver::new(rope_walk(motions, params, part)?);
+    driver.run_to_end()?;
+    return Ok(driver.sim().visited.len());
+}
+
+/// The rope over everywhere its tail has been.
+fn draw_walk(walk: &RopeWalk) -> String {
+    let mut drawing = walk.visited.clone();
+    for (knot, &ch) in draw_rope(&walk.rope).iter() {
+        drawing.insert(knot, ch);
+    }
+    drawing.to_string()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u32 = 9;
+    const PARAMS: &'static [Param] = PARAMS;
+
+    /// Each motion of the head: a direction and a number of steps.
+    type Input = Vec<(Direction, usize)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(content: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let parsed_input = content
+            .lines()
+            .map(|line| {
+                let s = line.split(' ').collect::<Vec<&str>>();
+                if s.len() != 2 {
+                    return Err(AocError::within(
+                        content,
+                        line,
+                        "expected a direction and a count",
+                    ));
+                }
+                let dir = s[0]
+                    .parse::<Direction>()
+                    .map_err(|err| AocError::within(content, s[0], err))?;
+                if s[1].starts_with('-') {
+                    return Err(AocError::within(
+                        content,
+                        s[1],
+                        "the count of steps can't be negative",
+                    ));
+                }
+                let n = s[1]
+                    .parse::<usize>()
+                    .map_err(|err| AocError::within(content, s[1], err.to_string()))?;
+                return Ok((dir, n));
+            })
+            .collect::<Result<Vec<(Direction, usize)>, AocError>>()?;
+        Ok(parsed_input)
+    }
+
+    fn part1(motions: &Self::Input, params: &Params) -> Result<usize, Box<dyn Error>> {
+        count_tail_positions(motions, params, Part::One)
+    }
+
+    fn part2(motions: &Self::Input, params: &Params) -> Result<usize, Box<dyn Error>> {
+        count_tail_positions(motions, params, Part::Two)
+    }
+
+    fn animate(
+        motions: &Self::Input,
+        params: &Params,
+        part: Part,
+        animator: &mut Animator,
+    ) -> Result<(), Box<dyn Error>> {
+        animator.set_palette(|ch| match ch {
+            'H' => Some(Colour::Red),
+            'T' => Some(Colour::Green),
+            '1'..='9' => Some(Colour::Yellow),
+            '#' => Some(Colour::Grey),
+            _ => None,
+        });
+        animator.play_driver(Driver::new(rope_walk(motions, params, part)?), draw_walk)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(Day09, "13", "1");
+
+    #[test]
+    fn test_negative_count() {
+        let err = Day09::parse("R 4\nU -2\n").unwrap_err();
+        let err = err.downcast_ref::<AocError>().unwrap();
+        assert_eq!((err.line, err.column, err.width), (2, 3, 2));
+    }
+}
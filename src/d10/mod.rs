@@ -0,0 +1,177 @@
+use crate::error::AocError;
+use crate::params::{Param, Params};
+use crate::parse::Span;
+use crate::simulation::{Driver, Simulation};
+use crate::solution::{Part, Solution};
+use crate::template::Template;
+use itertools::Itertools;
+use std::error::Error;
+use std::sync::LazyLock;
+
+#[derive(Clone)]
+pub struct State {
+    pub register_x: i32,
+    pub cycle_num: i32,
+}
+
+pub enum Command {
+    Noop,
+    Addx(i32),
+}
+
+static NOOP: LazyLock<Template> = LazyLock::new(|| Template::new("noop"));
+static ADDX: LazyLock<Template> = LazyLock::new(|| Template::new("addx {n}"));
+
+impl Command {
+    fn parse(line: Span) -> Result<Self, AocError> {
+        match line.as_str().split(' ').next() {
+            Some("noop") => {
+                NOOP.matches(line)?;
+                return Ok(Command::Noop);
+            }
+            Some("addx") => return Ok(Command::Addx(ADDX.matches(line)?.get("n")?)),
+            _ => return Err(line.error("Invalid command. Not noop or addx")),
+        }
+    }
+}
+
+const PARAMS: &[Param] = &[Param::new(
+    "cycles",
+    "20,60,100,140,180,220",
+    "cycles whose signal strengths part 1 adds up",
+)];
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    const PARAMS: &'static [Param] = PARAMS;
+
+    type Input = Vec<Command>;
+    type Answer1 = i32;
+    /// The six rows of the CRT, `#` for a lit pixel.
+    type Answer2 = String;
+
+    fn parse(content: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let commands = Span::new(content)
+            .lines()
+            .map(Command::parse)
+            .collect::<Result<Vec<Command>, AocError>>()?;
+        Ok(commands)
+    }
+
+    fn part1(commands: &Self::Input, params: &Params) -> Result<i32, Box<dyn Error>> {
+        let cycles: Vec<i32> = params.get_list("cycles", Part::One)?;
+        let signal_stength: i32 = states(commands)?
+            .into_iter()
+            .filter(|State { cycle_num, .. }| cycles.contains(cycle_num))
+            .map(
+                |State {
+                     cycle_num,
+                     register_x,
+                 }| cycle_num * register_x,
+            )
+            .sum();
+
+        return Ok(signal_stength);
+    }
+
+    fn part2(commands: &Self::Input, _: &Params) -> Result<String, Box<dyn Error>> {
+        let first = State {
+            register_x: 1,
+            cycle_num: 1,
+        };
+        let rows = std::iter::once(first)
+            .chain(states(commands)?)
+            .take(240)
+            .map(
+                |State {
+                     register_x,
+                     cycle_num,
+                 }| {
+                    let col = (cycle_num - 1) % 40;
+                    if (register_x - col).abs() <= 1 {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                },
+            )
+            .chunks(40)
+            .into_iter()
+            .map(String::from_iter)
+            .collect::<Vec<String>>();
+        Ok(rows.join("\n"))
+    }
+}
+
+/// The CPU running through `commands` one cycle at a time.
+pub struct Cpu<'a> {
+    pub state: State,
+    pub commands: &'a [Command],
+    /// The next command to start.
+    pub next: usize,
+    /// What an `addx` started last cycle will add at the end of this one.
+    pub pending: Option<i32>,
+}
+
+impl<'a> Cpu<'a> {
+    pub fn new(commands: &'a [Command]) -> Cpu<'a> {
+        Cpu {
+            state: State {
+                register_x: 1,
+                cycle_num: 1,
+            },
+            commands,
+            next: 0,
+            pending: None,
+        }
+    }
+}
+
+impl Simulation for Cpu<'_> {
+    type Snapshot = State;
+
+    fn step(&mut self) {
+        self.state.cycle_num += 1;
+        if let Some(n) = self.pending.take() {
+            self.state.register_x += n;
+            return;
+        }
+        if let Command::Addx(n) = self.commands[self.next] {
+            self.pending = Some(n);
+        }
+        self.next += 1;
+    }
+
+    fn is_done(&self) -> bool {
+        self.next == self.commands.len() && self.pending.is_none()
+    }
+
+    fn snapshot(&self) -> State {
+        self.state.clone()
+    }
+}
+
+/// The register after every cycle of running `commands`.
+pub fn states(commands: &[Command]) -> Result<Vec<State>, Box<dyn Error>> {
+    Ok(Driver::new(Cpu::new(commands)).snapshots()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(
+        Day10,
+        "13140",
+        concat!(
+            "##..##..##..##..##..##..##..##..##..##..\n",
+            "###...###...###...###...###...###...###.\n",
+            "####....####....####....####....####....\n",
+            "#####.....#####.....#####.....#####.....\n",
+            "######......######......######......####\n",
+            "#######.......#######.......#######....."
+        )
+    );
+}
@@ -0,0 +1,584 @@
+use crate::dot::{Dot, ToDot};
+use crate::error::AocError;
+use crate::params::{Param, Params};
+use crate::parse::Span;
+use crate::simulation::{Driver, Simulation};
+use crate::solution::{Part, Solution};
+use crate::template::Template;
+use crate::trace;
+use either::Either;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::LazyLock;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Old;
+
+#[derive(Debug, Clone)]
+pub struct Operation {
+    pub lhs: Either<Old, i64>,
+    pub rhs: Either<Old, i64>,
+    pub op: Op,
+}
+
+impl Operation {
+    /// The new worry level, or `None` if it doesn't fit in an `i64`.
+    pub fn call(&self, old_item: i64) -> Option<i64> {
+        let [lhs, rhs]: [i64; 2] = [self.lhs, self.rhs].map(|x| match x {
+            Either::Left(Old) => old_item,
+            Either::Right(y) => y,
+        });
+
+        match self.op {
+            Op::Add => lhs.checked_add(rhs),
+            Op::Sub => lhs.checked_sub(rhs),
+            Op::Mul => {
+                trace::trace!("multiply", lhs = lhs, rhs = rhs);
+                lhs.checked_mul(rhs)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    pub id: i64,
+    pub items: Vec<i64>,
+    pub operation: Operation,
+    pub divisible: i64,
+    pub if_true: i64,
+    pub if_false: i64,
+}
+
+impl Monkey {
+    pub fn test(&self, item: i64) -> i64 {
+        if item % self.divisible == 0 {
+            return self.if_true;
+        } else {
+            return self.if_false;
+        }
+    }
+}
+
+static MONKEY: LazyLock<Template> = LazyLock::new(|| {
+    Template::new(concat!(
+        "Monkey {id}:\n",
+        "  Starting items: {items}\n",
+        "  Operation: new = {lhs} {op} {rhs}\n",
+        "  Test: divisible by {divisible}\n",
+        "    If true: throw to monkey {if_true}\n",
+        "    If false: throw to monkey {if_false}",
+    ))
+});
+
+/// Parse one monkey from `block`, a paragraph of the input.
+fn parse_monkey(block: Span) -> Result<Monkey, AocError> {
+    let fields = MONKEY.matches(block)?;
+    let divisible = fields.get::<i64>("divisible")?;
+    if divisible <= 0 {
+        return Err(fields.span("divisible").error("divisor must be positive"));
+    }
+
+    let items = fields
+        .span("items")
+        .split(",")
+        .map(|item| item.parse::<i64>())
+        .collect::<Result<Vec<i64>, AocError>>()?;
+
+    let op = match fields.str("op") {
+        "+" => Op::Add,
+        "-" => Op::Sub,
+        "*" => Op::Mul,
+        _ => return Err(fields.span("op").error("expected +, - or *")),
+    };
+    let operand = |name: &str| match fields.str(name) {
+        "old" => Ok(Either::Left(Old)),
+        _ => fields
+            .get::<i64>(name)
+            .map(Either::Right)
+            .map_err(|_| fields.span(name).error("expected old or a number")),
+    };
+    let operation = Operation {
+        lhs: operand("lhs")?,
+        rhs: operand("rhs")?,
+        op,
+    };
+
+    let monkey = Monkey {
+        id: fields.get("id")?,
+        items,
+        operation,
+        divisible,
+        if_true: fields.get("if_true")?,
+        if_false: fields.get("if_false")?,
+    };
+    Ok(monkey)
+}
+
+fn parse(content: &str) -> Result<MonkeyMap, Box<dyn Error>> {
+    let blocks = Span::new(content).paragraphs().collect::<Vec<Span>>();
+    let monkeys = blocks
+        .iter()
+        .map(|&block| parse_monkey(block))
+        .collect::<Result<Vec<Monkey>, AocError>>()?;
+
+    // rounds visit monkeys in id order, and every throw has to land on one of them
+    let n = monkeys.len() as i64;
+    for (i, (monkey, block)) in monkeys.iter().zip(&blocks).enumerate() {
+        let lines = block.lines().collect::<Vec<Span>>();
+        if monkey.id != i as i64 {
+            return Err(lines[0].error(format!("expected monkey {}", i)).into());
+        }
+        for (target, line) in [(monkey.if_true, lines[4]), (monkey.if_false, lines[5])] {
+            if !(0..n).contains(&target) {
+                return Err(line.error(format!("there is no monkey {}", target)).into());
+            }
+        }
+    }
+
+    Ok(monkeys
+        .into_iter()
+        .map(|monkey| (monkey.id, monkey))
+        .collect())
+}
+
+pub type MonkeyMap = HashMap<i64, Monkey>;
+
+/// How worry levels are kept in check after each inspection.
+#[derive(Debug, Clone, Copy)]
+pub enum Relief {
+    DivideBy(i64),
+    /// Keep worry levels modulo this, which must be a multiple of every monkey's divisor.
+    Modulo(i64),
+}
+
+impl Relief {
+    fn apply(self, item: i64) -> i64 {
+        match self {
+            Relief::DivideBy(n) => item / n,
+            Relief::Modulo(n) => item % n,
+        }
+    }
+}
+
+/// A monkey's operation took an item's worry level past what an `i64` holds, which happens when
+/// there's too little relief for the number of rounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    pub monkey: i64,
+    pub item: i64,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "worry level overflowed when monkey {} inspected an item at {}",
+            self.monkey, self.item
+        )
+    }
+}
+
+impl Error for Overflow {}
+
+/// Monkey `id` inspects and throws each of its items in turn, calling `closure` with the monkey
+/// the item went to after each throw. Stops at the first item whose worry level overflows.
+pub fn take_turn<F>(
+    map: &mut MonkeyMap,
+    id: i64,
+    relief: Relief,
+    mut closure: F,
+) -> Result<(), Overflow>
+where
+    F: FnMut(&MonkeyMap, i64, i64),
+{
+    // for each item (<item_i>)
+    //   - operation(<item_i>)
+    //   - apply relief to <item_i>
+    //   - test worry level <item_i> and throw
+
+    while !map.get_mut(&id).unwrap().items.is_empty() {
+        // mutate the src monkey
+        let src_monkey = map.get_mut(&id).unwrap();
+        let item = src_monkey.items[0];
+        src_monkey.items[0] = src_monkey
+            .operation
+            .call(item)
+            .ok_or(Overflow { monkey: id, item })?;
+        src_monkey.items[0] = relief.apply(src_monkey.items[0]);
+        let throw_to = src_monkey.test(src_monkey.items[0]); // why does this work? taking an
+                                                             // immutable reference out while
+                                                             // already have a mutable one
+        let item_to_throw = src_monkey.items.remove(0);
+
+        // mutate the dst monkey
+        let dst_monkey = map.get_mut(&throw_to).unwrap();
+        dst_monkey.items.push(item_to_throw);
+        closure(map, id, throw_to);
+    }
+    Ok(())
+}
+
+pub fn take_round<F>(map: &mut MonkeyMap, relief: Relief, mut closure: F) -> Result<(), Overflow>
+where
+    F: FnMut(&MonkeyMap, i64, i64),
+{
+    for id in 0..map.len() {
+        take_turn(map, id as i64, relief, &mut closure)?;
+    }
+    Ok(())
+}
+
+/// The monkeys playing keep away, one round per step, counting how many items each inspects.
+pub struct KeepAway {
+    pub monkeys: MonkeyMap,
+    pub relief: Relief,
+    /// Monkey id to the number of items it has inspected.
+    pub inspections: HashMap<i64, i64>,
+    /// Items thrown from one monkey to another.
+    pub throws: HashMap<(i64, i64), i64>,
+    /// Set when a worry level overflows, which ends the game.
+    pub overflow: Option<Overflow>,
+}
+
+impl Simulation for KeepAway {
+    /// Every monkey's items.
+    type Snapshot = Vec<Vec<i64>>;
+
+    fn step(&mut self) {
+        let inspections = &mut self.inspections;
+        let throws = &mut self.throws;
+        let round = take_round(&mut self.monkeys, self.relief, |_, from, to| {
+            *inspections.entry(from).or_insert(0) += 1;
+            *throws.entry((from, to)).or_insert(0) += 1;
+        });
+        self.overflow = round.err();
+    }
+
+    fn is_done(&self) -> bool {
+        self.overflow.is_some()
+    }
+
+    fn snapshot(&self) -> Vec<Vec<i64>> {
+        (0..self.monkeys.len() as i64)
+            .map(|id| self.monkeys[&id].items.clone())
+            .collect()
+    }
+}
+
+/// Monkeys labelled with how many items they inspected, and an edge to each monkey they throw to
+/// labelled with the test that picks it and how many items went that way.
+impl ToDot for KeepAway {
+    fn to_dot(&self) -> Dot {
+        let mut dot = Dot::new("d11");
+        let monkeys = (0..self.monkeys.len() as i64).map(|id| &self.monkeys[&id]);
+        for monkey in monkeys.clone() {
+            let inspected = self.inspections.get(&monkey.id).copied().unwrap_or(0);
+            let label = format!("Monkey {}\ninspected {}", monkey.id, inspected);
+            dot.node(monkey.id, &[("label", label)]);
+        }
+        for monkey in monkeys {
+            let targets = [
+                (monkey.if_true, format!("divisible by {}", monkey.divisible)),
+                (monkey.if_false, String::from("otherwise")),
+            ];
+            for (to, test) in targets {
+                let thrown = self.throws.get(&(monkey.id, to)).copied().unwrap_or(0);
+                let label = format!("{}\n{} thrown", test, thrown);
+                dot.edge(
+                    monkey.id,
+                    to,
+                    &[("label", label), ("weight", thrown.to_string())],
+                );
+            }
+        }
+        dot
+    }
+}
+
+const PARAMS: &[Param] = &[
+    Param::per_part("rounds", "20", "10000", "rounds of keep away"),
+    Param::per_part(
+        "relief",
+        "3",
+        "1",
+        "what worry levels are divided by after each inspection",
+    ),
+];
+
+/// The rounds and relief the part plays with, from `params`. Without relief, worry levels are kept modulo the
+/// product of the divisors instead, which leaves every test's result the same but stops them
+/// growing without bound.
+fn rules(
+    monkey_map: &MonkeyMap,
+    params: &Params,
+    part: Part,
+) -> Result<(usize, Relief), Box<dyn Error>> {
+    let rounds = params.get("rounds", part)?;
+    let relief = match params.get_at_least::<i64>("relief", part, 1)? {
+        1 => Relief::Modulo(monkey_map.values().map(|monkey| monkey.divisible).product()),
+        n => Relief::DivideBy(n),
+    };
+    Ok((rounds, relief))
+}
+
+/// The game after `part`'s rounds.
+fn keep_away(
+    monkey_map: &MonkeyMap,
+    params: &Params,
+    part: Part,
+) -> Result<KeepAway, Box<dyn Error>> {
+    let (rounds, relief) = rules(monkey_map, params, part)?;
+    let mut driver = Driver::new(KeepAway {
+        monkeys: monkey_map.clone(),
+        relief,
+        inspections: HashMap::new(),
+        throws: HashMap::new(),
+        overflow: None,
+    });
+    driver.run(rounds)?;
+    let game = driver.into_inner();
+    match game.overflow {
+        Some(overflow) => Err(overflow.into()),
+        None => Ok(game),
+    }
+}
+
+/// Product of the two highest inspection counts after `part`'s rounds.
+fn monkey_business(
+    monkey_map: &MonkeyMap,
+    params: &Params,
+    part: Part,
+) -> Result<i64, Box<dyn Error>> {
+    let game = keep_away(monkey_map, params, part)?;
+    let mut inspections: Vec<i64> = game.inspections.values().copied().collect::<_>();
+    inspections.sort();
+    inspections.reverse();
+    match inspections[..] {
+        [a, b, ..] => Ok(a * b),
+        _ => Err("Bad inspections")?,
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    const PARAMS: &'static [Param] = PARAMS;
+
+    type Input = MonkeyMap;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(content: &str) -> Result<Self::Input, Box<dyn Error>> {
+        parse(content)
+    }
+
+    fn part1(monkey_map: &Self::Input, params: &Params) -> Result<i64, Box<dyn Error>> {
+        monkey_business(monkey_map, params, Part::One)
+    }
+
+    fn part2(monkey_map: &Self::Input, params: &Params) -> Result<i64, Box<dyn Error>> {
+        monkey_business(monkey_map, params, Part::Two)
+    }
+
+    fn graph(monkey_map: &Self::Input, params: &Params, part: Part) -> Result<Dot, Box<dyn Error>> {
+        Ok(keep_away(monkey_map, params, part)?.to_dot())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(Day11, "10605", "2713310158");
+
+    fn _get_data() -> MonkeyMap {
+        HashMap::from([
+            (
+                0,
+                Monkey {
+                    id: 0,
+                    items: vec![79, 98],
+                    operation: Operation {
+                        lhs: Either::Left(Old),
+                        rhs: Either::Right(19),
+                        op: Op::Mul,
+                    },
+                    divisible: 23,
+                    if_true: 2,
+                    if_false: 3,
+                },
+            ),
+            (
+                1,
+                Monkey {
+                    id: 1,
+                    items: vec![54, 65, 75, 74],
+                    operation: Operation {
+                        lhs: Either::Left(Old),
+                        rhs: Either::Right(6),
+                        op: Op::Add,
+                    },
+                    divisible: 19,
+                    if_true: 2,
+                    if_false: 0,
+                },
+            ),
+            (
+                2,
+                Monkey {
+                    id: 2,
+                    items: vec![79, 60, 97],
+                    operation: Operation {
+                        lhs: Either::Left(Old),
+                        rhs: Either::Left(Old), // 'old * old' implies both lhs and rhs are the old value
+                        op: Op::Mul,
+                    },
+                    divisible: 13,
+                    if_true: 1,
+                    if_false: 3,
+                },
+            ),
+            (
+                3,
+                Monkey {
+                    id: 3,
+                    items: vec![74],
+                    operation: Operation {
+                        lhs: Either::Left(Old),
+                        rhs: Either::Right(3),
+                        op: Op::Add,
+                    },
+                    divisible: 17,
+                    if_true: 0,
+                    if_false: 1,
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_1() {
+        let mut monkey_map = _get_data();
+
+        for round in 1..=10 {
+            take_round(&mut monkey_map, Relief::DivideBy(3), |_, _, _| {}).unwrap();
+
+            match round {
+                2 => {
+                    assert_eq!(
+                        monkey_map.get(&0).unwrap().items,
+                        vec![695, 10, 71, 135, 350]
+                    );
+                    assert_eq!(monkey_map.get(&1).unwrap().items, vec![43, 49, 58, 55, 362]);
+                    assert_eq!(monkey_map.get(&2).unwrap().items, Vec::<i64>::new());
+                    assert_eq!(monkey_map.get(&3).unwrap().items, Vec::<i64>::new());
+                }
+                3 => {
+                    assert_eq!(monkey_map.get(&0).unwrap().items, vec![16, 18, 21, 20, 122]);
+                    assert_eq!(
+                        monkey_map.get(&1).unwrap().items,
+                        vec![1468, 22, 150, 286, 739]
+                    );
+                    assert_eq!(monkey_map.get(&2).unwrap().items, Vec::<i64>::new());
+                    assert_eq!(monkey_map.get(&3).unwrap().items, Vec::<i64>::new());
+                }
+                4 => {
+                    assert_eq!(
+                        monkey_map.get(&0).unwrap().items,
+                        vec![491, 9, 52, 97, 248, 34]
+                    );
+                    assert_eq!(monkey_map.get(&1).unwrap().items, vec![39, 45, 43, 258]);
+                    assert_eq!(monkey_map.get(&2).unwrap().items, Vec::<i64>::new());
+                    assert_eq!(monkey_map.get(&3).unwrap().items, Vec::<i64>::new());
+                }
+                5 => {
+                    assert_eq!(
+                        monkey_map.get(&0).unwrap().items,
+                        vec![15, 17, 16, 88, 1037]
+                    );
+                    assert_eq!(
+                        monkey_map.get(&1).unwrap().items,
+                        vec![20, 110, 205, 524, 72]
+                    );
+                    assert_eq!(monkey_map.get(&2).unwrap().items, Vec::<i64>::new());
+                    assert_eq!(monkey_map.get(&3).unwrap().items, Vec::<i64>::new());
+                }
+                6 => {
+                    assert_eq!(monkey_map.get(&0).unwrap().items, vec![8, 70, 176, 26, 34]);
+                    assert_eq!(
+                        monkey_map.get(&1).unwrap().items,
+                        vec![481, 32, 36, 186, 2190]
+                    );
+                    assert_eq!(monkey_map.get(&2).unwrap().items, Vec::<i64>::new());
+                    assert_eq!(monkey_map.get(&3).unwrap().items, Vec::<i64>::new());
+                }
+                7 => {
+                    assert_eq!(
+                        monkey_map.get(&0).unwrap().items,
+                        vec![162, 12, 14, 64, 732, 17]
+                    );
+                    assert_eq!(monkey_map.get(&1).unwrap().items, vec![148, 372, 55, 72]);
+                    assert_eq!(monkey_map.get(&2).unwrap().items, Vec::<i64>::new());
+                    assert_eq!(monkey_map.get(&3).unwrap().items, Vec::<i64>::new());
+                }
+                8 => {
+                    assert_eq!(
+                        monkey_map.get(&0).unwrap().items,
+                        vec![51, 126, 20, 26, 136]
+                    );
+                    assert_eq!(
+                        monkey_map.get(&1).unwrap().items,
+                        vec![343, 26, 30, 1546, 36]
+                    );
+                    assert_eq!(monkey_map.get(&2).unwrap().items, Vec::<i64>::new());
+                    assert_eq!(monkey_map.get(&3).unwrap().items, Vec::<i64>::new());
+                }
+                9 => {
+                    assert_eq!(
+                        monkey_map.get(&0).unwrap().items,
+                        vec![116, 10, 12, 517, 14]
+                    );
+                    assert_eq!(
+                        monkey_map.get(&1).unwrap().items,
+                        vec![108, 267, 43, 55, 288]
+                    );
+                    assert_eq!(monkey_map.get(&2).unwrap().items, Vec::<i64>::new());
+                    assert_eq!(monkey_map.get(&3).unwrap().items, Vec::<i64>::new());
+                }
+                10 => {
+                    assert_eq!(monkey_map.get(&0).unwrap().items, vec![91, 16, 20, 98]);
+                    assert_eq!(
+                        monkey_map.get(&1).unwrap().items,
+                        vec![481, 245, 22, 26, 1092, 30]
+                    );
+                    assert_eq!(monkey_map.get(&2).unwrap().items, Vec::<i64>::new());
+                    assert_eq!(monkey_map.get(&3).unwrap().items, Vec::<i64>::new());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn test_overflow_is_an_error() {
+        let settings = [String::from("rounds.2=10000"), String::from("relief.2=3")];
+        let params = Params::set(PARAMS, &settings).unwrap();
+        let result = monkey_business(&_get_data(), &params, Part::Two);
+        let err = result.unwrap_err();
+        assert!(
+            err.to_string().starts_with("worry level overflowed"),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use crate::dot::{Dot, ToDot};
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::image::{Image, Rgb};
+use crate::params::Params;
+use crate::point::Point2;
+use crate::search::{bfs, BfsNode, BfsTree};
+use crate::solution::{Explained, Part, Solution, Witness};
+use std::error::Error;
+use std::rc::Rc;
+
+/// The heightmap, with elevations from 0 (a) to 25 (z).
+pub struct Board {
+    pub heights: Grid<i32>,
+    pub start: Point2,
+    pub end: Point2,
+}
+
+pub fn parse(content: &str) -> Result<Board, AocError> {
+    let squares = Grid::parse(content, |ch| match ch {
+        'S' | 'E' | 'a'..='z' => Ok(ch),
+        _ => Err(format!("unexpected square {:?}", ch)),
+    })?;
+    let start = squares
+        .position(|&ch| ch == 'S')
+        .ok_or_else(|| AocError::at_end(content, "couldn't find 'S' in input"))?;
+    let end = squares
+        .position(|&ch| ch == 'E')
+        .ok_or_else(|| AocError::at_end(content, "couldn't find 'E' in input"))?;
+    let heights = squares.map(|&ch| match ch {
+        'S' => 0,
+        'E' => 25,
+        _ => ((ch as u32) - 97) as i32,
+    });
+    Ok(Board {
+        heights,
+        start,
+        end,
+    })
+}
+
+/// A breadth-first search over the squares: up from `S` for part 1, or for part 2 down from `E`,
+/// so the first square at elevation a is the closest one.
+fn search(board: &Board, part: Part) -> impl Iterator<Item = Rc<BfsNode<Point2>>> + '_ {
+    let start = match part {
+        Part::One => board.start,
+        Part::Two => board.end,
+    };
+    bfs(start, move |&currentloc| {
+        board
+            .heights
+            .neighbours4(currentloc)
+            .filter(move |&newloc| match part {
+                Part::One => board.heights[newloc] <= board.heights[currentloc] + 1,
+                Part::Two => board.heights[currentloc] <= board.heights[newloc] + 1,
+            })
+    })
+}
+
+/// The shortest way up from `S` to `E`, or down from `E` to any square at elevation a.
+fn shortest_path(board: &Board, part: Part) -> Result<Rc<BfsNode<Point2>>, Box<dyn Error>> {
+    let found_node = match part {
+        Part::One => search(board, part)
+            .find(|node| node.val == board.end)
+            .ok_or("Could not find a path to end node")?,
+        Part::Two => search(board, part)
+            .find(|node| board.heights[node.val] == 0)
+            .ok_or("Could not find a path from any square at elevation a")?,
+    };
+    Ok(found_node)
+}
+
+/// The length of the part's shortest path, with the squares along it as `x,y`.
+fn explain_path(board: &Board, part: Part) -> Result<Explained<usize>, Box<dyn Error>> {
+    let found_node = shortest_path(board, part)?;
+    let path = found_node
+        .path()
+        .iter()
+        .map(Point2::to_string)
+        .collect::<Vec<String>>();
+    Ok(Explained::new(
+        found_node.depth,
+        Witness::new().with("path", path),
+    ))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+
+    type Input = Board;
+    type Answer1 = Explained<usize>;
+    type Answer2 = Explained<usize>;
+
+    fn parse(content: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse(content)?)
+    }
+
+    fn part1(board: &Self::Input, _: &Params) -> Result<Explained<usize>, Box<dyn Error>> {
+        explain_path(board, Part::One)
+    }
+
+    fn part2(board: &Self::Input, _: &Params) -> Result<Explained<usize>, Box<dyn Error>> {
+        explain_path(board, Part::Two)
+    }
+
+    /// The heightmap from dark low ground to white peaks, with the part's path drawn over it.
+    fn images(board: &Self::Input, _: &Params, part: Part) -> Result<Vec<Image>, Box<dyn Error>> {
+        let path = shortest_path(board, part)?.path();
+        let mut image = Image::from_grid(&board.heights, |&height| {
+            Rgb(20, 40, 90).lerp(Rgb(235, 235, 225), height as f64 / 25.0)
+        });
+        let ends = [path[0], path[path.len() - 1]];
+        image.overlay(path, Rgb::RED);
+        image.overlay(ends, Rgb::YELLOW);
+        Ok(vec![image])
+    }
+
+    /// Every square the part's search reaches, labelled with its distance from where it started.
+    fn graph(board: &Self::Input, _: &Params, part: Part) -> Result<Dot, Box<dyn Error>> {
+        Ok(search(board, part).collect::<BfsTree<_>>().to_dot())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(Day12, "31", "29");
+}
@@ -0,0 +1,239 @@
+use crate::error::AocError;
+use crate::params::Params;
+use crate::parse::Span;
+use crate::solution::{Explained, Solution, Witness};
+use std::error::Error;
+
+#[derive(Debug, Clone)]
+pub enum Packet {
+    List(Vec<Packet>),
+    Number(i32),
+}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Packet) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Packet) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Packet) -> std::cmp::Ordering {
+        use Packet::*;
+        match (self, other) {
+            (Number(x), Number(y)) => x.cmp(y),
+            (List(l1), List(l2)) => {
+                for (x, y) in l1.iter().zip(l2.iter()) {
+                    match x.cmp(y) {
+                        std::cmp::Ordering::Less => return std::cmp::Ordering::Less,
+                        std::cmp::Ordering::Greater => return std::cmp::Ordering::Greater,
+                        _ => {}
+                    }
+                }
+                if l1.len() != l2.len() {
+                    return l1.len().cmp(&l2.len());
+                }
+                return std::cmp::Ordering::Equal;
+            }
+            (l1 @ List(_), Number(n)) => l1.cmp(&List(vec![Number(*n)])),
+            (Number(n), l2 @ List(_)) => List(vec![Number(*n)]).cmp(l2),
+        }
+    }
+}
+
+pub mod parse {
+    use super::Packet;
+
+    /// What went wrong, and where: `at` indexes the chars that were passed to `parse_packet`.
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct ParseError {
+        pub at: usize,
+        pub message: String,
+    }
+
+    fn error<T>(at: usize, message: impl Into<String>) -> Result<T, ParseError> {
+        Err(ParseError {
+            at,
+            message: message.into(),
+        })
+    }
+
+    fn find_close_brace(s: &[char]) -> Result<usize, ParseError> {
+        if s.first() != Some(&'[') {
+            return error(0, "expected opening brace");
+        }
+
+        let mut n = 0;
+        for (i, ch) in s.iter().enumerate() {
+            match ch {
+                '[' => n += 1,
+                ']' => n -= 1,
+                _ => (),
+            }
+            if n == 0 {
+                return Ok(i);
+            }
+        }
+        error(0, "this brace is never closed")
+    }
+
+    pub fn parse_packet(s: &[char]) -> Result<(Packet, usize), ParseError> {
+        match s.first() {
+            Some(ch) if ch.is_ascii_digit() => {
+                let i = s.iter().take_while(|ch| ch.is_ascii_digit()).count();
+                let n: i32 = match s[0..i].iter().collect::<String>().parse() {
+                    Ok(n) => n,
+                    Err(err) => return error(0, err.to_string()),
+                };
+                return Ok((Packet::Number(n), i));
+            }
+            Some('[') => {
+                let close_bracket_idx = find_close_brace(s)?;
+                let mut idx = 1;
+
+                let mut packets = Vec::new();
+                while idx < close_bracket_idx {
+                    let (next_packet, offset) =
+                        parse_packet(&s[idx..]).map_err(|err| ParseError {
+                            at: err.at + idx,
+                            ..err
+                        })?;
+                    idx += offset;
+                    packets.push(next_packet);
+                    if s[idx] == ',' {
+                        idx += 1
+                    } else if idx == close_bracket_idx {
+                        // do nothing
+                    } else {
+                        return error(idx, "expected ',' or ']'");
+                    }
+                }
+                if idx != close_bracket_idx {
+                    return error(idx, "expected ']'");
+                }
+                idx += 1;
+                Ok((Packet::List(packets), idx))
+            }
+            Some(ch) => error(0, format!("unexpected character {:?}", ch)),
+            None => error(0, "expected a packet"),
+        }
+    }
+}
+
+/// Parse a whole line of `content` as a single packet.
+fn parse_line(content: &str, line: &str) -> Result<Packet, AocError> {
+    let chars = line.chars().collect::<Vec<char>>();
+    let err = match parse::parse_packet(&chars) {
+        Ok((packet, len)) if len == chars.len() => return Ok(packet),
+        Ok((_, len)) => parse::ParseError {
+            at: len,
+            message: String::from("unexpected text after the packet"),
+        },
+        Err(err) => err,
+    };
+    let start = line
+        .char_indices()
+        .nth(err.at)
+        .map_or(line.len(), |(i, _)| i);
+    let width = line[start..].chars().next().map_or(0, char::len_utf8);
+    Err(AocError::within(
+        content,
+        &line[start..start + width],
+        err.message,
+    ))
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+
+    /// The packet pairs, in input order.
+    type Input = Vec<(Packet, Packet)>;
+    type Answer1 = Explained<i32>;
+    type Answer2 = Explained<usize>;
+
+    fn parse(content: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let pairs = Span::new(content)
+            .paragraphs()
+            .map(|paragraph| {
+                let mut lines = paragraph.lines();
+                let mut packet = || match lines.next() {
+                    Some(line) => parse_line(content, line.as_str()),
+                    None => Err(paragraph.error_at_end("expected another packet")),
+                };
+                let (p1, p2) = (packet()?, packet()?);
+                if let Some(line) = lines.next() {
+                    return Err(line.error("expected a blank line"));
+                }
+                Ok((p1, p2))
+            })
+            .collect::<Result<Self::Input, AocError>>()?;
+        if pairs.is_empty() {
+            return Err(AocError::at_end(content, "expected another packet"))?;
+        }
+        Ok(pairs)
+    }
+
+    fn part1(pairs: &Self::Input, _: &Params) -> Result<Explained<i32>, Box<dyn Error>> {
+        let mut in_order = vec![];
+        for (i, (p1, p2)) in (1..).zip(pairs.iter()) {
+            if p1 <= p2 {
+                in_order.push(i);
+            }
+        }
+        let sum = in_order.iter().sum();
+        Ok(Explained::new(
+            sum,
+            Witness::new().with("in_order", in_order),
+        ))
+    }
+
+    fn part2(pairs: &Self::Input, _: &Params) -> Result<Explained<usize>, Box<dyn Error>> {
+        use Packet::*;
+        let dividers = [
+            List(vec![List(vec![Number(2)])]),
+            List(vec![List(vec![Number(6)])]),
+        ];
+        let mut packets: Vec<&Packet> = pairs
+            .iter()
+            .flat_map(|(p1, p2)| [p1, p2])
+            .chain(dividers.iter())
+            .collect();
+        packets.sort();
+        let positions = dividers
+            .iter()
+            .map(|divider| packets.iter().position(|&p| p == divider).unwrap() + 1)
+            .collect::<Vec<usize>>();
+        let decoder_key = positions.iter().product();
+        Ok(Explained::new(
+            decoder_key,
+            Witness::new().with("dividers", positions),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(Day13, "13", "140");
+
+    #[test]
+    fn test_parse_error_location() {
+        let err = Day13::parse("[1,2]\n[1,[2;3]]\n").unwrap_err();
+        let err = err.downcast::<AocError>().unwrap();
+        assert_eq!((err.line, err.column, err.width), (2, 6, 1));
+        assert_eq!(err.message, "expected ',' or ']'");
+
+        let err = Day13::parse("[1,2]\n").unwrap_err();
+        assert_eq!(err.downcast::<AocError>().unwrap().line, 1);
+    }
+}
@@ -0,0 +1,200 @@
+use crate::animate::{Animator, Colour};
+use crate::grid::Grid;
+use crate::image::{Image, Rgb};
+use crate::params::Params;
+use crate::point::{Direction, Point2};
+use crate::solution::{Part, Solution};
+use crate::sparse_grid::{Bounds, SparseGrid};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Copy, Clone)]
+pub enum Item {
+    Empty,
+    Elf,
+}
+
+/// The order elves consider moving in on the first round; it rotates by one every round.
+pub const ORDER: [Direction; 4] = [
+    Direction::North,
+    Direction::South,
+    Direction::West,
+    Direction::East,
+];
+
+/// The three cells an elf at `loc` must find empty before moving towards `direction`.
+fn lookahead(direction: Direction, loc: Point2) -> [Point2; 3] {
+    [
+        loc + direction.anticlockwise(),
+        loc + direction,
+        loc + direction.clockwise(),
+    ]
+}
+
+impl Item {
+    pub fn from_char(ch: char) -> Result<Self, String> {
+        match ch {
+            '.' => Ok(Item::Empty),
+            '#' => Ok(Item::Elf),
+            _ => Err(format!("Can't convert char \"{}\" to Item", ch)),
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Item::Empty => '.',
+            Item::Elf => '#',
+        }
+    }
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+/// Elves spread out without limit, so they're kept on an unbounded grid.
+pub type Elves = SparseGrid<Item>;
+
+/// Every elf on the grid.
+pub fn elves(grid: &Grid<Item>) -> Elves {
+    SparseGrid::from_grid(grid, |item| matches!(item, Item::Elf))
+}
+
+/// Play one round, considering directions starting from `ORDER[round % 4]`. Returns
+/// whether any elf moved.
+fn play_round(elves: &mut Elves, round: usize) -> bool {
+    let mut proposals: HashMap<Point2, Vec<Point2>> = HashMap::new();
+    for elf in elves.positions() {
+        let alone = elves.neighbours8(elf).next().is_none();
+        if alone {
+            continue;
+        }
+        let proposal = (0..4)
+            .map(|i| ORDER[(round + i) % 4])
+            .find(|&d| lookahead(d, elf).iter().all(|&loc| !elves.contains(loc)));
+        if let Some(d) = proposal {
+            proposals.entry(elf + d).or_default().push(elf);
+        }
+    }
+
+    // Elves proposing the same cell all stay put.
+    let mut moved = false;
+    for (to, from) in proposals {
+        if let [elf] = from[..] {
+            elves.remove(elf);
+            elves.insert(to, Item::Elf);
+            moved = true;
+        }
+    }
+    moved
+}
+
+/// The elves before the first round and after each one: part 1's ten rounds, or part 2's every
+/// round until the elves stop moving.
+fn rounds(grid: &Grid<Item>, part: Part) -> impl Iterator<Item = Elves> {
+    let mut elves = elves(grid);
+    let mut round = 0;
+    let mut done = false;
+    std::iter::once(elves.clone()).chain(std::iter::from_fn(move || {
+        if done || (part == Part::One && round == 10) {
+            return None;
+        }
+        done = !play_round(&mut elves, round);
+        round += 1;
+        Some(elves.clone())
+    }))
+}
+
+/// Each round as a picture of elves on the ground, all framed by the ground every round covers so
+/// the frames line up.
+fn draw_rounds(rounds: &[Elves]) -> Vec<Image> {
+    let Some(bounds) = rounds
+        .iter()
+        .filter_map(Elves::bounds)
+        .reduce(|a, b| Bounds {
+            min: Point2::new(a.min.x.min(b.min.x), a.min.y.min(b.min.y)),
+            max: Point2::new(a.max.x.max(b.max.x), a.max.y.max(b.max.y)),
+        })
+    else {
+        return vec![];
+    };
+    rounds
+        .iter()
+        .map(|elves| {
+            let mut image = Image::new(
+                bounds.width() as usize,
+                bounds.height() as usize,
+                Rgb(60, 45, 30),
+            );
+            image.overlay(
+                elves.positions().map(|elf| elf - bounds.min),
+                Rgb(80, 200, 90),
+            );
+            image
+        })
+        .collect()
+}
+
+fn empty_ground(elves: &Elves) -> i64 {
+    let area = elves.bounds().map_or(0, |bounds| bounds.area());
+    area - elves.len() as i64
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u32 = 23;
+
+    type Input = Grid<Item>;
+    type Answer1 = i64;
+    type Answer2 = usize;
+
+    fn parse(content: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(Grid::parse(content, Item::from_char)?)
+    }
+
+    fn part1(grid: &Self::Input, _: &Params) -> Result<i64, Box<dyn Error>> {
+        let mut elves = elves(grid);
+        for round in 0..10 {
+            play_round(&mut elves, round);
+        }
+        Ok(empty_ground(&elves))
+    }
+
+    fn part2(grid: &Self::Input, _: &Params) -> Result<usize, Box<dyn Error>> {
+        let mut elves = elves(grid);
+        let round = (0..).find(|&round| !play_round(&mut elves, round)).unwrap();
+        Ok(round + 1)
+    }
+
+    fn animate(
+        grid: &Self::Input,
+        _: &Params,
+        part: Part,
+        animator: &mut Animator,
+    ) -> Result<(), Box<dyn Error>> {
+        animator.set_palette(|ch| match ch {
+            '#' => Some(Colour::Green),
+            _ => Some(Colour::Grey),
+        });
+        for elves in rounds(grid, part) {
+            animator.show(&elves.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// The same rounds as `animate`, a frame each.
+    fn images(grid: &Self::Input, _: &Params, part: Part) -> Result<Vec<Image>, Box<dyn Error>> {
+        Ok(draw_rounds(&rounds(grid, part).collect::<Vec<_>>()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(Day23, "110", "20");
+}
@@ -0,0 +1,324 @@
+use crate::animate::{Animator, Colour};
+use crate::cycle;
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::params::Params;
+use crate::point::{Direction, Point2};
+use crate::simulation::{Driver, Simulation};
+use crate::solution::{Part, Solution};
+use crate::trace;
+use std::collections::HashSet;
+use std::error::Error;
+use std::iter::FromIterator;
+
+#[derive(PartialEq, Eq)]
+pub enum Space {
+    Empty,
+    Blizzard(Direction),
+    Wall,
+}
+
+pub type I = i64;
+fn modulus(a: I, b: I) -> I {
+    return ((a % b) + b) % b;
+}
+
+/// The valley at t=0, with its entrance and exit.
+pub struct Valley {
+    pub grid: Grid<Space>,
+    pub startloc: Point2,
+    pub endloc: Point2,
+    /// Minutes until the blizzards are all back where they started.
+    pub period: usize,
+}
+
+fn parse(content: &str) -> Result<Valley, AocError> {
+    let grid = Grid::parse(content, |ch| match ch {
+        '>' | '<' | 'v' | '^' => Ok(Space::Blizzard(Direction::from_char(ch)?)),
+        '#' => Ok(Space::Wall),
+        '.' => Ok(Space::Empty),
+        _ => Err(format!("unexpected char {:?}", ch)),
+    })?;
+    // blizzards wrap round inside the walls, so there has to be an inside
+    if grid.width() < 3 {
+        let line = content.lines().next().unwrap();
+        let message = "expected the valley to be at least 3 columns wide";
+        return Err(AocError::within(content, line, message));
+    }
+    if grid.height() < 3 {
+        let message = "expected the valley to be at least 3 rows tall";
+        return Err(AocError::at_end(content, message));
+    }
+    let opening = |row: usize, message: &str| {
+        grid.row(row)
+            .iter()
+            .position(|space| *space == Space::Empty)
+            .map(|col| Point2::new(col as I, row as I))
+            .ok_or_else(|| {
+                let line = content.lines().nth(row).unwrap();
+                AocError::within(content, line, message)
+            })
+    };
+    let startloc = opening(0, "expected an opening in the top wall")?;
+    let endloc = opening(grid.height() - 1, "expected an opening in the bottom wall")?;
+    let period = blizzard_period(&grid);
+    Ok(Valley {
+        grid,
+        startloc,
+        endloc,
+        period,
+    })
+}
+
+/// How long the blizzards in `grid` take to come back round to where they are at t=0.
+fn blizzard_period(grid: &Grid<Space>) -> usize {
+    let (height, width) = (grid.height() as I, grid.width() as I);
+    let (directions, positions): (Vec<Direction>, Vec<Point2>) = grid
+        .iter()
+        .filter_map(|(pos, space)| match space {
+            Space::Blizzard(direction) => Some((*direction, pos)),
+            _ => None,
+        })
+        .unzip();
+    let step = |positions: &Vec<Point2>| {
+        positions
+            .iter()
+            .zip(&directions)
+            .map(|(&pos, &direction)| {
+                let Point2 { x, y } = pos + direction.offset();
+                Point2::new(
+                    modulus(x - 1, width - 2) + 1,
+                    modulus(y - 1, height - 2) + 1,
+                )
+            })
+            .collect::<Vec<Point2>>()
+    };
+    cycle::brent(positions, step).length
+}
+
+impl Valley {
+    /// The blizzards over `pos` at time `t`. Each one heading a given way that's here now started
+    /// `t` steps back, wrapping around inside the walls.
+    fn blizzards_at(&self, pos: Point2, t: I) -> impl Iterator<Item = Direction> + '_ {
+        let (height, width) = (self.grid.height() as I, self.grid.width() as I);
+        Direction::ORTHOGONAL.into_iter().filter(move |&direction| {
+            let Point2 { x, y } = pos - direction.offset() * t;
+            let origin = Point2::new(
+                modulus(x - 1, width - 2) + 1,
+                modulus(y - 1, height - 2) + 1,
+            );
+            self.grid[origin] == Space::Blizzard(direction)
+        })
+    }
+}
+
+/// Everywhere the expedition could be at time `t`, spreading out one minute per step and
+/// dodging blizzards, until it could be at `to`.
+pub struct Expedition<'a> {
+    pub valley: &'a Valley,
+    pub to: Point2,
+    pub t: I,
+    pub possible_locations: HashSet<Point2>,
+}
+
+impl Simulation for Expedition<'_> {
+    type Snapshot = HashSet<Point2>;
+
+    fn step(&mut self) {
+        let valley = self.valley;
+        let (startloc, endloc) = (valley.startloc, valley.endloc);
+        let (height, width) = (valley.grid.height() as I, valley.grid.width() as I);
+        self.t += 1;
+        let t = self.t;
+
+        // spread over and remove
+        self.possible_locations = self
+            .possible_locations
+            .iter()
+            .flat_map(|&loc| {
+                trace::trace!("possible location", t = t, row = loc.y, col = loc.x);
+                // moves off the top or bottom row are filtered out below
+                let newlocs = std::iter::once(loc).chain(loc.neighbours4());
+
+                newlocs.filter(move |&newloc| {
+                    if newloc == startloc || newloc == endloc {
+                        return true;
+                    }
+                    let Point2 { x, y } = newloc;
+                    if y < 1 || y > height - 2 || x < 1 || x > width - 2 {
+                        return false;
+                    }
+                    valley.blizzards_at(newloc, t).next().is_none()
+                })
+            })
+            .collect::<_>();
+    }
+
+    fn is_done(&self) -> bool {
+        self.possible_locations.is_empty() || self.possible_locations.contains(&self.to)
+    }
+
+    fn snapshot(&self) -> HashSet<Point2> {
+        self.possible_locations.clone()
+    }
+}
+
+/// The valley drawn at the expedition's time, with `E` everywhere it could be and each blizzard
+/// drawn as in the input, or as the number of them where several cross.
+fn draw(expedition: &Expedition) -> String {
+    let valley = expedition.valley;
+    let mut drawing = Grid::new(valley.grid.width(), valley.grid.height(), '.');
+    for (pos, space) in valley.grid.iter() {
+        drawing[pos] = match space {
+            Space::Wall => '#',
+            _ if expedition.possible_locations.contains(&pos) => 'E',
+            _ if pos == valley.startloc || pos == valley.endloc => '.',
+            _ => {
+                let blizzards = valley.blizzards_at(pos, expedition.t).collect::<Vec<_>>();
+                match blizzards[..] {
+                    [] => '.',
+                    [Direction::North] => '^',
+                    [Direction::East] => '>',
+                    [Direction::South] => 'v',
+                    [Direction::West] => '<',
+                    _ => char::from_digit(blizzards.len() as u32, 10).unwrap(),
+                }
+            }
+        };
+    }
+    drawing.to_string()
+}
+
+/// The expedition leaving `from` at `start_time`, ready to run until it could be at `to`.
+fn crossing(
+    valley: &Valley,
+    from: Point2,
+    to: Point2,
+    start_time: I,
+) -> Driver<'_, Expedition<'_>> {
+    // t=0 is the first frame
+    let expedition = Expedition {
+        valley,
+        to,
+        t: start_time,
+        possible_locations: HashSet::from_iter(vec![from]),
+    };
+    // the blizzards repeat every `period` minutes, so by the time every square has been tried at
+    // every point in the cycle there's nothing new left to try
+    let (height, width) = (valley.grid.height(), valley.grid.width());
+    Driver::new(expedition).max_steps(valley.period * height * width)
+}
+
+/// When a finished crossing got where it was going.
+fn arrival(expedition: &Expedition) -> Result<I, Box<dyn Error>> {
+    if !expedition.possible_locations.contains(&expedition.to) {
+        Err("there is no way through the valley")?;
+    }
+    trace::debug!("crossed the valley", to = %expedition.to, t = expedition.t);
+    Ok(expedition.t)
+}
+
+/// The earliest time `to` can be reached when leaving `from` at `start_time`.
+fn shortest_time(
+    valley: &Valley,
+    from: Point2,
+    to: Point2,
+    start_time: I,
+) -> Result<I, Box<dyn Error>> {
+    let mut driver = crossing(valley, from, to, start_time);
+    driver.run_to_end()?;
+    arrival(driver.sim())
+}
+
+/// Where each crossing starts and ends: just the once for part 1, but part 2 goes there, back
+/// for the snacks, and there again.
+fn legs(valley: &Valley, part: Part) -> Vec<(Point2, Point2)> {
+    let there = (valley.startloc, valley.endloc);
+    let back = (valley.endloc, valley.startloc);
+    match part {
+        Part::One => vec![there],
+        Part::Two => vec![there, back, there],
+    }
+}
+
+/// When the last of the part's legs ends.
+fn total_time(valley: &Valley, part: Part) -> Result<I, Box<dyn Error>> {
+    let mut t = 0;
+    for (from, to) in legs(valley, part) {
+        t = shortest_time(valley, from, to, t)?;
+    }
+    Ok(t)
+}
+
+fn palette(ch: char) -> Option<Colour> {
+    match ch {
+        '#' => Some(Colour::Grey),
+        'E' => Some(Colour::Green),
+        '.' => None,
+        _ => Some(Colour::Cyan),
+    }
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u32 = 24;
+
+    type Input = Valley;
+    type Answer1 = I;
+    type Answer2 = I;
+
+    fn parse(content: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse(content)?)
+    }
+
+    fn part1(valley: &Self::Input, _: &Params) -> Result<I, Box<dyn Error>> {
+        total_time(valley, Part::One)
+    }
+
+    fn part2(valley: &Self::Input, _: &Params) -> Result<I, Box<dyn Error>> {
+        total_time(valley, Part::Two)
+    }
+
+    fn animate(
+        valley: &Self::Input,
+        _: &Params,
+        part: Part,
+        animator: &mut Animator,
+    ) -> Result<(), Box<dyn Error>> {
+        animator.set_palette(palette);
+        let mut t = 0;
+        for (from, to) in legs(valley, part) {
+            let expedition = animator.play_driver(crossing(valley, from, to, t), draw)?;
+            t = arrival(&expedition)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(Day24, "18", "54");
+
+    #[test]
+    fn test_blizzard_period() {
+        let content = std::fs::read_to_string(crate::examples::example_path(24)).unwrap();
+        // 6 columns and 4 rows between the walls
+        assert_eq!(parse(&content).unwrap().period, 12);
+    }
+
+    #[test]
+    fn test_valley_too_small() {
+        let message = |content: &str| parse(content).err().unwrap().message;
+        assert_eq!(
+            message("#.\n#.\n#.\n"),
+            "expected the valley to be at least 3 columns wide"
+        );
+        assert_eq!(
+            message("#.###\n###.#\n"),
+            "expected the valley to be at least 3 rows tall"
+        );
+    }
+}
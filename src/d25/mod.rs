@@ -0,0 +1,126 @@
+use crate::error::AocError;
+use crate::params::Params;
+use crate::solution::{Solution, Unsolved};
+use std::error::Error;
+
+pub fn from_snafu(snafu: &str) -> Result<isize, AocError> {
+    if snafu.is_empty() {
+        return Err(AocError::at_end(snafu, "expected a SNAFU number"));
+    }
+    let mut total: isize = 0;
+    for (i, c) in snafu.char_indices() {
+        let digit = match c {
+            '0' | '1' | '2' => c.to_digit(10).unwrap() as isize,
+            '-' => -1,
+            '=' => -2,
+            _ => {
+                let message = "SNAFU digits are 0, 1, 2, - and =";
+                return Err(AocError::within(
+                    snafu,
+                    &snafu[i..i + c.len_utf8()],
+                    message,
+                ));
+            }
+        };
+        total = total
+            .checked_mul(5)
+            .and_then(|total| total.checked_add(digit))
+            .ok_or_else(|| AocError::within(snafu, snafu, "SNAFU number is too big"))?;
+    }
+    return Ok(total);
+}
+
+/// `n` in SNAFU, least significant digit first: each digit is `n`'s remainder mod 5, taken as
+/// -2 to 2, and what's left once that's taken away is the rest.
+pub fn to_snafu(n: isize) -> Result<String, AocError> {
+    if n == 0 {
+        return Ok(String::from("0"));
+    }
+    let mut digits = vec![];
+    let mut rest = n;
+    while rest != 0 {
+        let (c, digit) = match rest.rem_euclid(5) {
+            0 => ('0', 0),
+            1 => ('1', 1),
+            2 => ('2', 2),
+            3 => ('=', -2),
+            _ => ('-', -1),
+        };
+        digits.push(c);
+        rest = rest.checked_sub(digit).ok_or_else(|| {
+            let text = n.to_string();
+            AocError::within(&text, &text, "too big to write in SNAFU")
+        })? / 5;
+    }
+    Ok(digits.into_iter().rev().collect())
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u32 = 25;
+
+    /// The fuel requirements, as SNAFU numbers.
+    type Input = Vec<String>;
+    type Answer1 = String;
+    type Answer2 = Unsolved;
+
+    fn parse(content: &str) -> Result<Self::Input, Box<dyn Error>> {
+        for line in content.lines() {
+            if line.is_empty() {
+                return Err(AocError::within(content, line, "expected a SNAFU number"))?;
+            }
+            if let Some((i, c)) = line.char_indices().find(|&(_, c)| !"012-=".contains(c)) {
+                let digit = &line[i..i + c.len_utf8()];
+                let message = "SNAFU digits are 0, 1, 2, - and =";
+                return Err(AocError::within(content, digit, message))?;
+            }
+            from_snafu(line).map_err(|err| AocError::within(content, line, err.message))?;
+        }
+        Ok(content.lines().map(String::from).collect())
+    }
+
+    fn part1(numbers: &Self::Input, _: &Params) -> Result<String, Box<dyn Error>> {
+        let mut total: isize = 0;
+        for number in numbers {
+            total = total
+                .checked_add(from_snafu(number)?)
+                .ok_or("the total is too big")?;
+        }
+        Ok(to_snafu(total)?)
+    }
+
+    /// Day 25 only has the one puzzle.
+    fn part2(_: &Self::Input, _: &Params) -> Result<Unsolved, Box<dyn Error>> {
+        Err(Unsolved)?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::examples::example_tests!(Day25, "2=-1=0");
+
+    #[test]
+    fn test_snafu() {
+        for (n, snafu) in [(0, "0"), (3, "1="), (2022, "1=11-2"), (-8, "=2")] {
+            assert_eq!(from_snafu(snafu).unwrap(), n);
+            assert_eq!(to_snafu(n).unwrap(), snafu);
+        }
+        let max = to_snafu(isize::MAX).unwrap();
+        assert_eq!(from_snafu(&max).unwrap(), isize::MAX);
+
+        let long = "2".repeat(40);
+        assert_eq!(
+            from_snafu(&long).unwrap_err().message,
+            "SNAFU number is too big"
+        );
+        assert_eq!(
+            to_snafu(isize::MIN).unwrap_err().message,
+            "too big to write in SNAFU"
+        );
+        let err = Day25::parse(&format!("1=\n{}\n", long)).unwrap_err();
+        assert_eq!(err.downcast_ref::<AocError>().unwrap().line, 2);
+    }
+}
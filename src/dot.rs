@@ -0,0 +1,132 @@
+//! Writing puzzle models out as Graphviz DOT, to look at with `dot -Tsvg`.
+//!
+//! Models implement `ToDot` by adding their nodes and edges to a `Dot`, with whatever attributes
+//! help: a label on every node, and edges labelled with a weight where there is one. Node ids are
+//! always quoted, so any text will do.
+use std::fmt;
+use std::fmt::Display;
+
+/// A model that can be drawn as a graph.
+pub trait ToDot {
+    fn to_dot(&self) -> Dot;
+}
+
+/// Attribute names and values, like `label` or `shape`.
+type Attrs = Vec<(String, String)>;
+
+/// A directed graph, written out in the order its nodes and edges were added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dot {
+    name: String,
+    nodes: Vec<(String, Attrs)>,
+    edges: Vec<(String, String, Attrs)>,
+}
+
+impl Dot {
+    pub fn new(name: impl Display) -> Dot {
+        Dot {
+            name: name.to_string(),
+            nodes: vec![],
+            edges: vec![],
+        }
+    }
+
+    /// Add a node with attributes such as `label` or `shape`.
+    pub fn node(&mut self, id: impl Display, attrs: &[(&str, String)]) {
+        self.nodes.push((id.to_string(), owned(attrs)));
+    }
+
+    pub fn edge(&mut self, from: impl Display, to: impl Display, attrs: &[(&str, String)]) {
+        self.edges
+            .push((from.to_string(), to.to_string(), owned(attrs)));
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+fn owned(attrs: &[(&str, String)]) -> Attrs {
+    attrs
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.clone()))
+        .collect()
+}
+
+/// `s` as a double-quoted DOT string.
+fn quote(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for ch in s.chars() {
+        match ch {
+            '"' => quoted += "\\\"",
+            '\\' => quoted += "\\\\",
+            '\n' => quoted += "\\n",
+            _ => quoted.push(ch),
+        }
+    }
+    quoted + "\""
+}
+
+fn write_attrs(f: &mut fmt::Formatter, attrs: &[(String, String)]) -> fmt::Result {
+    if attrs.is_empty() {
+        return Ok(());
+    }
+    let attrs = attrs
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, quote(value)))
+        .collect::<Vec<String>>();
+    write!(f, " [{}]", attrs.join(", "))
+}
+
+impl Display for Dot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "digraph {} {{", quote(&self.name))?;
+        for (id, attrs) in &self.nodes {
+            write!(f, "    {}", quote(id))?;
+            write_attrs(f, attrs)?;
+            writeln!(f, ";")?;
+        }
+        for (from, to, attrs) in &self.edges {
+            write!(f, "    {} -> {}", quote(from), quote(to))?;
+            write_attrs(f, attrs)?;
+            writeln!(f, ";")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let mut dot = Dot::new("d07");
+        dot.node(0, &[("label", String::from("/\n48381165"))]);
+        dot.node(
+            "b.txt",
+            &[
+                ("label", String::from("say \"hi\"")),
+                ("shape", String::from("note")),
+            ],
+        );
+        dot.edge(0, "b.txt", &[]);
+        dot.edge(0, 0, &[("label", 3.to_string())]);
+        assert_eq!((dot.node_count(), dot.edge_count()), (2, 2));
+        assert_eq!(
+            dot.to_string(),
+            concat!(
+                "digraph \"d07\" {\n",
+                "    \"0\" [label=\"/\\n48381165\"];\n",
+                "    \"b.txt\" [label=\"say \\\"hi\\\"\", shape=\"note\"];\n",
+                "    \"0\" -> \"b.txt\";\n",
+                "    \"0\" -> \"0\" [label=\"3\"];\n",
+                "}\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::error::Error;
+use std::fmt;
+
+/// Puzzle input that a day's parser rejected, pinned to the line and column it went wrong at.
+///
+/// Parsers build one with `AocError::within`, pointing at the offending slice of their input; the
+/// day is filled in by the registry when the error passes back through it. Displaying the error
+/// prints the line with the offending text underlined:
+///
+/// ```text
+/// day 09, line 3, column 1: unknown direction "X"
+///   |
+/// 3 | X 4
+///   | ^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AocError {
+    pub day: Option<u32>,
+    /// 1-based.
+    pub line: usize,
+    /// 1-based, counted in characters.
+    pub column: usize,
+    /// How many characters from `column` to underline, at least 1.
+    pub width: usize,
+    /// The whole of the offending line.
+    pub text: String,
+    pub message: String,
+}
+
+impl AocError {
+    /// An error at byte `offset` into `content`, underlining `len` bytes (or one character if the
+    /// span is empty, e.g. at the end of a line). Spans running past the end of the line are cut
+    /// short there.
+    pub fn at_offset(content: &str, offset: usize, len: usize, message: impl Into<String>) -> Self {
+        let offset = offset.min(content.len());
+        let line_start = content[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = content[offset..]
+            .find('\n')
+            .map_or(content.len(), |i| offset + i);
+        let text = content[line_start..line_end].trim_end_matches('\r');
+        let span_end = (offset + len).min(line_start + text.len()).max(offset);
+        AocError {
+            day: None,
+            line: content[..line_start].matches('\n').count() + 1,
+            column: content[line_start..offset].chars().count() + 1,
+            width: content[offset..span_end].chars().count().max(1),
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// An error pointing at `token`, which must be a slice of `content` (as returned by `lines`,
+    /// `split` and friends). If it isn't, the error points at the start of `content`.
+    pub fn within(content: &str, token: &str, message: impl Into<String>) -> Self {
+        let start = content.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize).wrapping_sub(start);
+        if offset <= content.len() && offset + token.len() <= content.len() {
+            AocError::at_offset(content, offset, token.len(), message)
+        } else {
+            AocError::at_offset(content, 0, 0, message)
+        }
+    }
+
+    /// An error just past the last character of `content`, for input that stops too early.
+    pub fn at_end(content: &str, message: impl Into<String>) -> Self {
+        let trimmed = content.trim_end();
+        AocError::at_offset(content, trimmed.len(), 0, message)
+    }
+
+    pub fn in_day(mut self, day: u32) -> Self {
+        self.day = Some(day);
+        self
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {:02}, ", day)?;
+        }
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.width)
+        )
+    }
+}
+
+impl Error for AocError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_within() {
+        let content = "R 4\nU 4\nX 3\n";
+        let line = content.lines().nth(2).unwrap();
+        let err = AocError::within(content, &line[..1], "unknown direction \"X\"").in_day(9);
+        assert_eq!(
+            err,
+            AocError {
+                day: Some(9),
+                line: 3,
+                column: 1,
+                width: 1,
+                text: String::from("X 3"),
+                message: String::from("unknown direction \"X\""),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "day 09, line 3, column 1: unknown direction \"X\"\n  |\n3 | X 3\n  | ^"
+        );
+    }
+
+    #[test]
+    fn test_at_end() {
+        let err = AocError::at_end("abc\ndef\n", "expected more");
+        assert_eq!((err.line, err.column, err.width), (2, 4, 1));
+        assert_eq!(err.text, "def");
+
+        let err = AocError::within("abc", "not a slice", "oops");
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+}
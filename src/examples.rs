@@ -0,0 +1,54 @@
+//! Helpers for testing each day against the worked example from its puzzle text, which lives
+//! next to the real input as `src/dNN/example`.
+
+use crate::solution::{DynSolution, Part, Solution};
+use std::fs;
+use std::path::PathBuf;
+
+pub fn example_path(day: u32) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join(format!("d{:02}", day))
+        .join("example")
+}
+
+/// `S`'s answer to one part of its example, rendered as the runner would print it.
+pub fn answer<S>(solution: &S, part: Part) -> String
+where
+    S: Solution + DynSolution,
+{
+    let path = example_path(S::DAY);
+    let content = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("could not read {}: {}", path.display(), err));
+    let input = DynSolution::parse(solution, &content).unwrap();
+    input
+        .part(part)
+        .unwrap_or_else(|err| panic!("part {}: {}", part, err))
+        .to_string()
+}
+
+/// Generate `test_example_part1` (and `test_example_part2` when a second answer is given)
+/// checking a day's answers on its example. Attributes written before the solution, such
+/// as `#[ignore]`, are put on each generated test.
+macro_rules! example_tests {
+    ($(#[$attr:meta])* $solution:path, $part1:expr) => {
+        #[test]
+        $(#[$attr])*
+        fn test_example_part1() {
+            let answer = crate::examples::answer(&$solution, crate::solution::Part::One);
+            assert_eq!(answer, $part1);
+        }
+    };
+    ($(#[$attr:meta])* $solution:path, $part1:expr, $part2:expr) => {
+        crate::examples::example_tests!($(#[$attr])* $solution, $part1);
+
+        #[test]
+        $(#[$attr])*
+        fn test_example_part2() {
+            let answer = crate::examples::answer(&$solution, crate::solution::Part::Two);
+            assert_eq!(answer, $part2);
+        }
+    };
+}
+
+pub(crate) use example_tests;
@@ -0,0 +1,281 @@
+use crate::error::AocError;
+use crate::point::{Direction, Point2};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A dense, rectangular grid stored row-major. Cells are addressed by `Point2`, with `x` the
+/// column and `y` the row, so `(0, 0)` is the top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid made from `cells` in row-major order.
+    pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), width * height, "cells must fill the grid");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_cells(width, height, vec![fill; width * height])
+    }
+
+    /// Parse one cell per character, one row per line. Rows must all be the same length, and
+    /// `cell` rejects characters by returning an error message.
+    pub fn parse<F>(content: &str, mut cell: F) -> Result<Grid<T>, AocError>
+    where
+        F: FnMut(char) -> Result<T, String>,
+    {
+        let first = content
+            .lines()
+            .next()
+            .filter(|line| !line.is_empty())
+            .ok_or_else(|| AocError::at_end(content, "expected a grid"))?;
+        let width = first.chars().count();
+        let mut cells = vec![];
+        let mut height = 0;
+        for line in content.lines() {
+            if line.chars().count() != width {
+                let message = format!("expected {} columns like the first row", width);
+                return Err(AocError::within(content, line, message));
+            }
+            for (i, ch) in line.char_indices() {
+                let value = cell(ch)
+                    .map_err(|err| AocError::within(content, &line[i..i + ch.len_utf8()], err))?;
+                cells.push(value);
+            }
+            height += 1;
+        }
+        Ok(Grid::from_cells(width, height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, Point2 { x, y }: Point2) -> bool {
+        0 <= x && (x as usize) < self.width && 0 <= y && (y as usize) < self.height
+    }
+
+    fn index_of(&self, pos: Point2) -> Option<usize> {
+        if self.contains(pos) {
+            Some(pos.y as usize * self.width + pos.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, pos: Point2) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Point2) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Every position on the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point2> {
+        let width = self.width as i64;
+        (0..self.height as i64).flat_map(move |y| (0..width).map(move |x| Point2::new(x, y)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The first position, row by row, whose cell matches.
+    pub fn position(&self, mut pred: impl FnMut(&T) -> bool) -> Option<Point2> {
+        self.iter().find(|(_, cell)| pred(cell)).map(|(pos, _)| pos)
+    }
+
+    /// The neighbours of `pos` that are on the grid, north first and going clockwise.
+    pub fn neighbours4(&self, pos: Point2) -> impl Iterator<Item = Point2> + '_ {
+        pos.neighbours4().filter(|&pos| self.contains(pos))
+    }
+
+    /// The neighbours of `pos` that are on the grid, diagonals included.
+    pub fn neighbours8(&self, pos: Point2) -> impl Iterator<Item = Point2> + '_ {
+        pos.neighbours8().filter(|&pos| self.contains(pos))
+    }
+
+    pub fn row(&self, r: usize) -> &[T] {
+        &self.cells[r * self.width..(r + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, c: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(c < self.width, "column {} is off the grid", c);
+        self.cells.iter().skip(c).step_by(self.width)
+    }
+
+    /// The cells met walking from `from` by `step` until falling off the grid, not including
+    /// `from` itself.
+    pub fn ray(&self, from: Point2, step: Point2) -> impl Iterator<Item = (Point2, &T)> {
+        std::iter::successors(Some(from), move |&pos| Some(pos + step))
+            .skip(1)
+            .map_while(|pos| self.get(pos).map(|cell| (pos, cell)))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::from_cells(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// The cells met walking from `from` towards `direction`, not including `from` itself.
+    pub fn walk(&self, from: Point2, direction: Direction) -> impl Iterator<Item = (Point2, &T)> {
+        self.ray(from, direction.offset())
+    }
+
+    /// The grid flipped about its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|c| self.column(c).cloned())
+            .collect();
+        Grid::from_cells(self.height, self.width, cells)
+    }
+
+    /// The grid turned a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|c| self.column(c).rev().cloned())
+            .collect();
+        Grid::from_cells(self.height, self.width, cells)
+    }
+
+    /// The grid turned a quarter turn anticlockwise.
+    pub fn rotate_ccw(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|c| self.column(c).cloned())
+            .collect();
+        Grid::from_cells(self.height, self.width, cells)
+    }
+}
+
+impl<T> Index<Point2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point2) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} is off the {}x{} grid", pos, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, pos: Point2) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} is off the {}x{} grid", pos, width, height),
+        }
+    }
+}
+
+/// One line per row, with each cell's `Display` written side by side.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (r, row) in self.rows().enumerate() {
+            if r > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(content: &str) -> Grid<u32> {
+        Grid::parse(content, |ch| {
+            ch.to_digit(10).ok_or(format!("{:?} isn't a digit", ch))
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_get() {
+        let mut grid = digits("123\n456\n");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Point2::new(2, 1)), Some(&6));
+        assert_eq!(grid.get(Point2::new(0, 2)), None);
+        assert_eq!(grid.get(Point2::new(-1, 0)), None);
+        *grid.get_mut(Point2::ORIGIN).unwrap() = 9;
+        assert_eq!(grid[Point2::ORIGIN], 9);
+        assert_eq!(grid.to_string(), "923\n456");
+
+        let err = Grid::parse("12\n3\n", Ok::<char, String>).unwrap_err();
+        assert_eq!(err.line, 2);
+        let err = Grid::parse("12\n3x\n", |ch| {
+            ch.to_digit(10).ok_or(String::from("digit"))
+        })
+        .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits("123\n456\n789\n");
+        assert_eq!(
+            grid.neighbours4(Point2::ORIGIN).collect::<Vec<_>>(),
+            vec![Point2::new(1, 0), Point2::new(0, 1)]
+        );
+        assert_eq!(grid.neighbours4(Point2::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(Point2::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8(Point2::new(2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = digits("123\n456\n789\n");
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6, 9]);
+        assert_eq!(
+            grid.walk(Point2::new(0, 2), Direction::NorthEast)
+                .collect::<Vec<_>>(),
+            vec![(Point2::new(1, 1), &5), (Point2::new(2, 0), &3)]
+        );
+        assert_eq!(grid.ray(Point2::ORIGIN, Point2::new(0, -1)).count(), 0);
+    }
+
+    #[test]
+    fn test_transpose_and_rotate() {
+        let grid = digits("123\n456\n");
+        assert_eq!(grid.transpose(), digits("14\n25\n36\n"));
+        assert_eq!(grid.rotate_cw(), digits("41\n52\n63\n"));
+        assert_eq!(grid.rotate_ccw(), digits("36\n25\n14\n"));
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+}
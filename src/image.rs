@@ -0,0 +1,297 @@
+//! Writing grids out as pictures, in binary PPM or PNG, without pulling in an image crate.
+//!
+//! A day turns its `Grid` into an `Image` with a function giving each cell's colour, draws
+//! anything on top with `overlay` (d12's path over the heightmap, say), then scales it up so a
+//! cell is more than one pixel. PNGs are written with uncompressed deflate blocks, so they're
+//! big, but every viewer can open them.
+use crate::grid::Grid;
+use crate::point::Point2;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GREY: Rgb = Rgb(128, 128, 128);
+    pub const RED: Rgb = Rgb(220, 40, 40);
+    pub const YELLOW: Rgb = Rgb(250, 210, 40);
+
+    /// The colour `t` of the way from `self` to `other`, with `t` clamped to `0..=1`.
+    pub fn lerp(self, other: Rgb, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Rgb(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// Binary PPM (`P6`).
+    Ppm,
+    Png,
+}
+
+impl ImageFormat {
+    /// The format going by `path`'s extension.
+    pub fn from_path(path: &Path) -> Option<ImageFormat> {
+        match path.extension()?.to_str()? {
+            "ppm" => Some(ImageFormat::Ppm),
+            "png" => Some(ImageFormat::Png),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+/// A picture, stored row-major from the top left like a `Grid`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, fill: Rgb) -> Image {
+        Image {
+            width,
+            height,
+            pixels: vec![fill; width * height],
+        }
+    }
+
+    /// One pixel per cell of `grid`, coloured by `colour`.
+    pub fn from_grid<T>(grid: &Grid<T>, colour: impl Fn(&T) -> Rgb) -> Image {
+        Image {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.rows().flatten().map(colour).collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, pos: Point2) -> Option<Rgb> {
+        self.index_of(pos).map(|i| self.pixels[i])
+    }
+
+    /// Colour the pixel at `pos`, if it's in the picture.
+    pub fn set(&mut self, pos: Point2, colour: Rgb) {
+        if let Some(i) = self.index_of(pos) {
+            self.pixels[i] = colour;
+        }
+    }
+
+    /// Draw `points` in `colour` over what's there. Points are pixels, so overlay before scaling
+    /// to draw in grid cells.
+    pub fn overlay(&mut self, points: impl IntoIterator<Item = Point2>, colour: Rgb) {
+        for pos in points {
+            self.set(pos, colour);
+        }
+    }
+
+    /// The picture with every pixel blown up into a `factor` by `factor` square.
+    pub fn scaled(&self, factor: usize) -> Image {
+        assert!(factor > 0, "can't scale an image by 0");
+        let mut pixels = Vec::with_capacity(self.pixels.len() * factor * factor);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            let scaled_row = row
+                .iter()
+                .flat_map(|&pixel| std::iter::repeat_n(pixel, factor))
+                .collect::<Vec<Rgb>>();
+            for _ in 0..factor {
+                pixels.extend_from_slice(&scaled_row);
+            }
+        }
+        Image {
+            width: self.width * factor,
+            height: self.height * factor,
+            pixels,
+        }
+    }
+
+    fn index_of(&self, Point2 { x, y }: Point2) -> Option<usize> {
+        if 0 <= x && (x as usize) < self.width && 0 <= y && (y as usize) < self.height {
+            Some(y as usize * self.width + x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for &Rgb(r, g, b) in &self.pixels {
+            bytes.extend_from_slice(&[r, g, b]);
+        }
+        bytes
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut header = vec![];
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, then the only compression, filtering and interlacing there are
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        // every row starts with its filter type, 0 for none
+        let mut raw = Vec::with_capacity(self.height * (self.width * 3 + 1));
+        for row in self.pixels.chunks(self.width.max(1)) {
+            raw.push(0);
+            for &Rgb(r, g, b) in row {
+                raw.extend_from_slice(&[r, g, b]);
+            }
+        }
+
+        let mut bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut bytes, b"IHDR", &header);
+        png_chunk(&mut bytes, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut bytes, b"IEND", &[]);
+        bytes
+    }
+
+    pub fn encode(&self, format: ImageFormat) -> Vec<u8> {
+        match format {
+            ImageFormat::Ppm => self.to_ppm(),
+            ImageFormat::Png => self.to_png(),
+        }
+    }
+
+    pub fn save(&self, path: &Path, format: ImageFormat) -> io::Result<()> {
+        fs::write(path, self.encode(format))
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))
+    }
+}
+
+/// Save a sequence of frames next to `path`, numbered from 0: `out.png` becomes `out-0000.png`,
+/// `out-0001.png` and so on. Returns the paths written.
+pub fn save_frames(frames: &[Image], path: &Path, format: ImageFormat) -> io::Result<Vec<PathBuf>> {
+    let stem = path.file_stem().map_or_else(
+        || String::from("frame"),
+        |stem| stem.to_string_lossy().into_owned(),
+    );
+    let digits = frames.len().saturating_sub(1).to_string().len().max(4);
+    frames
+        .iter()
+        .enumerate()
+        .map(|(i, frame)| {
+            let name = format!("{}-{:0digits$}.{}", stem, i, format.extension());
+            let path = path.with_file_name(name);
+            frame.save(&path, format)?;
+            Ok(path)
+        })
+        .collect()
+}
+
+fn png_chunk(bytes: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    bytes.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = bytes.len();
+    bytes.extend_from_slice(kind);
+    bytes.extend_from_slice(data);
+    let crc = crc32(&bytes[start..]);
+    bytes.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// `data` as a zlib stream of stored (uncompressed) deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 0xffff;
+    let mut bytes = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        // an empty stream still needs one final block
+        bytes.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        bytes.push(last as u8);
+        bytes.extend_from_slice(&len.to_le_bytes());
+        bytes.extend_from_slice(&(!len).to_le_bytes());
+        bytes.extend_from_slice(block);
+    }
+    bytes.extend_from_slice(&adler32(data).to_be_bytes());
+    bytes
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % MOD;
+        b = (b + a) % MOD;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn test_grid_overlay_and_scale() {
+        let grid = Grid::from_cells(2, 1, vec![0, 10]);
+        let mut image = Image::from_grid(&grid, |&n| Rgb::BLACK.lerp(Rgb::WHITE, n as f64 / 10.0));
+        image.overlay([Point2::new(0, 0), Point2::new(5, 5)], Rgb::RED);
+        assert_eq!(image.to_ppm(), b"P6\n2 1\n255\n\xdc\x28\x28\xff\xff\xff");
+
+        let scaled = image.scaled(2);
+        assert_eq!((scaled.width(), scaled.height()), (4, 2));
+        assert_eq!(scaled.get(Point2::new(1, 1)), Some(Rgb::RED));
+        assert_eq!(scaled.get(Point2::new(2, 1)), Some(Rgb::WHITE));
+        assert_eq!(scaled.get(Point2::new(4, 0)), None);
+    }
+
+    #[test]
+    fn test_png() {
+        let png = Image::new(2, 2, Rgb::WHITE).to_png();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x02\0\0\0\x02\x08\x02"));
+        assert!(png.ends_with(b"\0\0\0\0IEND\xae\x42\x60\x82"));
+
+        // two rows of a filter byte and two white pixels, in one final stored block
+        let idat = &png[33..png.len() - 12];
+        assert_eq!(&idat[4..8], b"IDAT");
+        assert_eq!(&idat[8..15], &[0x78, 0x01, 1, 14, 0, !14, 0xff]);
+        assert_eq!(
+            u32::from_be_bytes(idat[0..4].try_into().unwrap()),
+            2 + 5 + 14 + 4
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Directory holding everyone's inputs, laid out like `src/`: `<dir>/dNN/input`.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// Where to read `day`'s input from. An explicit `--input` wins (`-` meaning stdin), then
+    /// `AOC_INPUT_DIR`, then the input checked in next to the day's source.
+    pub fn for_day(day: u32, input: Option<&str>) -> InputSource {
+        match input {
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(PathBuf::from(path)),
+            None => match env::var_os(INPUT_DIR_VAR) {
+                Some(dir) => InputSource::File(day_path(Path::new(&dir), day)),
+                None => InputSource::File(default_path(day)),
+            },
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        let result = match self {
+            InputSource::Stdin => {
+                let mut content = String::new();
+                io::stdin().read_to_string(&mut content).map(|_| content)
+            }
+            InputSource::File(path) => fs::read_to_string(path),
+        };
+        result
+            .map_err(|err| io::Error::new(err.kind(), format!("could not read {}: {}", self, err)))
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+pub fn day_path(dir: &Path, day: u32) -> PathBuf {
+    dir.join(format!("d{:02}", day)).join("input")
+}
+
+/// The input checked in at `src/dNN/input`, found from the crate root rather than the working
+/// directory.
+pub fn default_path(day: u32) -> PathBuf {
+    day_path(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explicit_input_wins() {
+        assert_eq!(InputSource::for_day(5, Some("-")), InputSource::Stdin);
+        assert_eq!(
+            InputSource::for_day(5, Some("other/input")),
+            InputSource::File(PathBuf::from("other/input"))
+        );
+    }
+
+    #[test]
+    fn test_day_path() {
+        assert_eq!(
+            day_path(Path::new("inputs"), 7),
+            PathBuf::from("inputs/d07/input")
+        );
+        assert!(default_path(12).ends_with("src/d12/input"));
+    }
+}
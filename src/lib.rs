@@ -0,0 +1,43 @@
+pub mod animate;
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod config;
+pub mod cycle;
+pub mod dot;
+pub mod error;
+#[cfg(test)]
+mod examples;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod params;
+pub mod parse;
+pub mod point;
+pub mod registry;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod simulation;
+pub mod solution;
+pub mod sparse_grid;
+pub mod submit;
+pub mod template;
+pub mod trace;
+
+pub mod d01;
+pub mod d02;
+pub mod d03;
+pub mod d04;
+pub mod d05;
+pub mod d06;
+pub mod d07;
+pub mod d08;
+pub mod d09;
+pub mod d10;
+pub mod d11;
+pub mod d12;
+pub mod d13;
+pub mod d23;
+pub mod d24;
+pub mod d25;
@@ -0,0 +1,264 @@
+//! Named puzzle parameters, for the numbers a puzzle picks that could just as well be others:
+//! d11's rounds, d06's window size, d07's disk size. Each day declares its parameters with a
+//! default, and parts look them up by name in the `Params` they're handed:
+//!
+//! ```text
+//! const PARAMS: &[Param] = &[Param::per_part("rounds", "20", "10000", "rounds of keep away")];
+//!
+//! let rounds: usize = params.get("rounds", part)?;
+//! ```
+//!
+//! `aoc run --set window_size=8` overrides a default for that run. A parameter with a
+//! different default in each part is set one part at a time, as `rounds.1=` or `rounds.2=`;
+//! `rounds.2=` also works for one whose parts share a default.
+use crate::solution::Part;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    /// The value each part uses unless it's overridden, as it would be written on the command
+    /// line.
+    pub defaults: [&'static str; 2],
+    pub help: &'static str,
+}
+
+impl Param {
+    /// A parameter with the same default in both parts.
+    pub const fn new(name: &'static str, default: &'static str, help: &'static str) -> Param {
+        Param::per_part(name, default, default, help)
+    }
+
+    pub const fn per_part(
+        name: &'static str,
+        part1: &'static str,
+        part2: &'static str,
+        help: &'static str,
+    ) -> Param {
+        Param {
+            name,
+            defaults: [part1, part2],
+            help,
+        }
+    }
+
+    pub fn default(&self, part: Part) -> &'static str {
+        match part {
+            Part::One => self.defaults[0],
+            Part::Two => self.defaults[1],
+        }
+    }
+
+    fn is_per_part(&self) -> bool {
+        self.defaults[0] != self.defaults[1]
+    }
+}
+
+/// The override key for `name` in `part`.
+fn part_key(name: &str, part: Part) -> String {
+    format!("{}.{}", name, part)
+}
+
+/// The values of a day's parameters for one run: their defaults, with any overrides from
+/// `--set`. Parts are handed one and look their parameters up in it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    params: &'static [Param],
+    overrides: HashMap<String, String>,
+}
+
+impl Params {
+    /// `params` with their defaults.
+    pub fn defaults(params: &'static [Param]) -> Params {
+        Params {
+            params,
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// `params` with some overridden by `name=value` or `name.<part>=value` settings. Fails if
+    /// a setting isn't in that form, names a parameter that isn't in `params`, or sets both
+    /// parts of one with a default per part.
+    pub fn set(params: &'static [Param], settings: &[String]) -> Result<Params, String> {
+        let mut overrides = HashMap::new();
+        for setting in settings {
+            let (key, value) = setting
+                .split_once('=')
+                .ok_or_else(|| format!("expected name=value, got {:?}", setting))?;
+            let (name, part) = match key.rsplit_once('.') {
+                Some((name, "1")) => (name, Some(Part::One)),
+                Some((name, "2")) => (name, Some(Part::Two)),
+                _ => (key, None),
+            };
+            let param =
+                params
+                    .iter()
+                    .find(|param| param.name == name)
+                    .ok_or_else(|| match params {
+                        [] => format!("there is no parameter {}, this day has none", name),
+                        _ => format!(
+                            "there is no parameter {}, expected one of:\n{}",
+                            name,
+                            describe(params)
+                        ),
+                    })?;
+            if part.is_none() && param.is_per_part() {
+                return Err(format!(
+                    "{} has a default for each part, set {} or {}",
+                    name,
+                    part_key(name, Part::One),
+                    part_key(name, Part::Two)
+                ));
+            }
+            overrides.insert(key.to_string(), value.to_string());
+        }
+        Ok(Params { params, overrides })
+    }
+
+    /// Parameter `name` for `part`, from an override for that part or else one for both parts
+    /// if there is one. Fails if the day doesn't declare `name`.
+    pub fn get<T>(&self, name: &str, part: Part) -> Result<T, Box<dyn Error>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let param = self
+            .params
+            .iter()
+            .find(|param| param.name == name)
+            .ok_or_else(|| format!("no parameter {} was declared", name))?;
+        let value = self
+            .overrides
+            .get(&part_key(name, part))
+            .or_else(|| self.overrides.get(name))
+            .map_or(param.default(part), String::as_str);
+        let parsed = value
+            .parse()
+            .map_err(|err| format!("parameter {}={}: {}", name, value, err))?;
+        Ok(parsed)
+    }
+
+    /// Parameter `name` for `part`, failing unless it's at least `min`.
+    pub fn get_at_least<T>(&self, name: &str, part: Part, min: T) -> Result<T, Box<dyn Error>>
+    where
+        T: FromStr + PartialOrd + Display,
+        T::Err: Display,
+    {
+        let value: T = self.get(name, part)?;
+        if value < min {
+            Err(format!(
+                "parameter {}={}: must be at least {}",
+                name, value, min
+            ))?;
+        }
+        Ok(value)
+    }
+
+    /// Parameter `name` for `part` as a comma-separated list.
+    pub fn get_list<T>(&self, name: &str, part: Part) -> Result<Vec<T>, Box<dyn Error>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value: String = self.get(name, part)?;
+        value
+            .split(',')
+            .map(|item| {
+                item.trim()
+                    .parse()
+                    .map_err(|err| format!("parameter {}={}: {}", name, value, err).into())
+            })
+            .collect()
+    }
+}
+
+/// One line per parameter: its name, its defaults and what it's for.
+pub fn describe(params: &[Param]) -> String {
+    params
+        .iter()
+        .map(|param| {
+            let setting = match param.defaults {
+                [one, two] if one == two => format!("{}={}", param.name, one),
+                [one, two] => format!(
+                    "{}={}, {}={}",
+                    part_key(param.name, Part::One),
+                    one,
+                    part_key(param.name, Part::Two),
+                    two
+                ),
+            };
+            format!("  {}  {}", setting, param.help)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[
+        Param::per_part("rounds", "20", "10000", "rounds to play"),
+        Param::new("cycles", "20,60", "cycles to sample"),
+    ];
+
+    #[test]
+    fn test_get_and_set() {
+        let params = Params::defaults(PARAMS);
+        assert_eq!(params.get::<usize>("rounds", Part::One).unwrap(), 20);
+        assert_eq!(params.get::<usize>("rounds", Part::Two).unwrap(), 10000);
+        assert_eq!(
+            params.get_list::<i32>("cycles", Part::One).unwrap(),
+            [20, 60]
+        );
+
+        let params = Params::set(
+            PARAMS,
+            &[String::from("rounds.2=5"), String::from("cycles=1, 2,3")],
+        )
+        .unwrap();
+        assert_eq!(params.get::<usize>("rounds", Part::One).unwrap(), 20);
+        assert_eq!(params.get::<usize>("rounds", Part::Two).unwrap(), 5);
+        assert_eq!(
+            params.get_list::<i32>("cycles", Part::Two).unwrap(),
+            [1, 2, 3]
+        );
+
+        let err = Params::set(PARAMS, &[String::from("round=5")]).unwrap_err();
+        assert_eq!(
+            err,
+            concat!(
+                "there is no parameter round, expected one of:\n",
+                "  rounds.1=20, rounds.2=10000  rounds to play\n",
+                "  cycles=20,60  cycles to sample",
+            )
+        );
+        assert!(Params::set(PARAMS, &[String::from("rounds")]).is_err());
+        assert_eq!(
+            Params::set(PARAMS, &[String::from("rounds=5")]).unwrap_err(),
+            "rounds has a default for each part, set rounds.1 or rounds.2"
+        );
+
+        let params = Params::set(PARAMS, &[String::from("rounds.1=lots")]).unwrap();
+        let err = params.get::<usize>("rounds", Part::One).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "parameter rounds=lots: invalid digit found in string"
+        );
+        let params = Params::set(PARAMS, &[String::from("rounds.1=0")]).unwrap();
+        let err = params
+            .get_at_least::<usize>("rounds", Part::One, 1)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "parameter rounds=0: must be at least 1");
+        assert_eq!(
+            params
+                .get::<usize>("round", Part::One)
+                .unwrap_err()
+                .to_string(),
+            "no parameter round was declared"
+        );
+    }
+}
@@ -0,0 +1,232 @@
+//! Helpers for the shapes puzzle inputs come in: lines of one value each, blank-line separated
+//! paragraphs, and values split in two or more on a separator. Everything works on `Span`s, pieces
+//! of the input that remember the whole of it, so any error points at the right line.
+use crate::error::AocError;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The input with Windows line endings and trailing whitespace taken off every line, and any
+/// blank lines at the end dropped. Days are handed their input like this, so they only ever have
+/// to deal with `\n`.
+pub fn normalize(content: &str) -> String {
+    let mut normalized = String::with_capacity(content.len());
+    for line in content.lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    let len = normalized.trim_end().len();
+    normalized.truncate(len);
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+/// One value per line.
+pub fn lines_as<T>(content: &str) -> Result<Vec<T>, AocError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    Span::new(content).lines_as()
+}
+
+/// The paragraphs of `content`, as slices of it.
+pub fn paragraphs(content: &str) -> impl Iterator<Item = &str> {
+    Span::new(content).paragraphs().map(|span| span.as_str())
+}
+
+/// A slice of the input, along with the whole input it came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    content: &'a str,
+    text: &'a str,
+}
+
+impl<'a> Span<'a> {
+    /// All of `content`.
+    pub fn new(content: &'a str) -> Span<'a> {
+        Span {
+            content,
+            text: content,
+        }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// A piece of this span, which must be a slice of it.
+    pub fn within(&self, text: &'a str) -> Span<'a> {
+        Span {
+            content: self.content,
+            text,
+        }
+    }
+
+    /// An error pointing at this span.
+    pub fn error(&self, message: impl Into<String>) -> AocError {
+        AocError::within(self.content, self.text, message)
+    }
+
+    /// An error just past the end of this span, for a piece of the input that stops too early.
+    pub fn error_at_end(&self, message: impl Into<String>) -> AocError {
+        let trimmed = self.text.trim_end();
+        AocError::within(self.content, &trimmed[trimmed.len()..], message)
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = Span<'a>> + 'a {
+        let span = *self;
+        self.text.lines().map(move |line| span.within(line))
+    }
+
+    /// Runs of lines separated by one or more blank lines, without the newline ending the last
+    /// line of each.
+    pub fn paragraphs(&self) -> Paragraphs<'a> {
+        Paragraphs {
+            span: *self,
+            rest: self.text,
+        }
+    }
+
+    /// The span's text, leading and trailing whitespace aside, as a `T`.
+    pub fn parse<T>(&self) -> Result<T, AocError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let s = self.text.trim();
+        s.parse::<T>()
+            .map_err(|err| AocError::within(self.content, s, err.to_string()))
+    }
+
+    /// One value per line. Blank lines are values too, and are likely to fail to parse.
+    pub fn lines_as<T>(&self) -> Result<Vec<T>, AocError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.lines().map(|line| line.parse()).collect()
+    }
+
+    /// The pieces between each `sep`.
+    pub fn split(&self, sep: &'a str) -> impl Iterator<Item = Span<'a>> + 'a {
+        let span = *self;
+        self.text.split(sep).map(move |piece| span.within(piece))
+    }
+
+    /// The text either side of the first `sep`.
+    pub fn split_once(&self, sep: &str) -> Result<(Span<'a>, Span<'a>), AocError> {
+        let (left, right) = self
+            .text
+            .split_once(sep)
+            .ok_or_else(|| self.error(format!("expected {:?}", sep)))?;
+        Ok((self.within(left), self.within(right)))
+    }
+
+    /// The text either side of the first `sep`, each parsed.
+    pub fn split_pair<A, B>(&self, sep: &str) -> Result<(A, B), AocError>
+    where
+        A: FromStr,
+        A::Err: Display,
+        B: FromStr,
+        B::Err: Display,
+    {
+        let (left, right) = self.split_once(sep)?;
+        Ok((left.parse()?, right.parse()?))
+    }
+
+    /// Exactly `N` pieces separated by `sep`.
+    pub fn split_n<const N: usize>(&self, sep: &str) -> Result<[Span<'a>; N], AocError> {
+        let pieces = self.text.split(sep).collect::<Vec<&str>>();
+        let pieces: [&str; N] = pieces
+            .try_into()
+            .map_err(|_| self.error(format!("expected {} parts separated by {:?}", N, sep)))?;
+        Ok(pieces.map(|piece| self.within(piece)))
+    }
+}
+
+/// Iterator returned by `Span::paragraphs`.
+pub struct Paragraphs<'a> {
+    span: Span<'a>,
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Paragraphs<'a> {
+    type Item = Span<'a>;
+
+    fn next(&mut self) -> Option<Span<'a>> {
+        // byte offsets into `rest`
+        let mut start = None;
+        let mut end = 0;
+        let mut offset = 0;
+        for line in self.rest.split_inclusive('\n') {
+            let blank = line.trim().is_empty();
+            match (start, blank) {
+                (None, true) => {}
+                (Some(_), true) => break,
+                (None, false) => start = Some(offset),
+                (Some(_), false) => {}
+            }
+            if !blank {
+                end = offset + line.trim_end_matches(['\n', '\r']).len();
+            }
+            offset += line.len();
+        }
+        let Some(start) = start else {
+            self.rest = "";
+            return None;
+        };
+        let paragraph = &self.rest[start..end];
+        self.rest = &self.rest[end..];
+        Some(self.span.within(paragraph))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("a \r\n\r\nb\t\r\n\r\n\r\n"), "a\n\nb\n");
+        assert_eq!(normalize("a"), "a\n");
+        assert_eq!(normalize("\n\n"), "");
+    }
+
+    #[test]
+    fn test_paragraphs_and_lines() {
+        let content = "\n1\n2\n\n\n3\nx\n";
+        assert_eq!(
+            paragraphs(content).collect::<Vec<&str>>(),
+            vec!["1\n2", "3\nx"]
+        );
+
+        let mut paragraphs = Span::new(content).paragraphs();
+        let first = paragraphs.next().unwrap();
+        assert_eq!(first.lines_as::<i32>(), Ok(vec![1, 2]));
+        let err = paragraphs.next().unwrap().lines_as::<i32>().unwrap_err();
+        assert_eq!((err.line, err.column), (7, 1));
+        assert_eq!(paragraphs.next(), None);
+
+        assert_eq!(lines_as::<u8>("4\n 5 \n"), Ok(vec![4, 5]));
+    }
+
+    #[test]
+    fn test_split() {
+        let content = "2-4,6-8\n5";
+        let [first, second] = Span::new(content).lines().collect::<Vec<_>>()[..] else {
+            panic!("expected two lines");
+        };
+        let [left, right] = first.split_n::<2>(",").unwrap();
+        assert_eq!(left.split_pair::<u32, u32>("-"), Ok((2, 4)));
+        assert_eq!(right.split_pair::<u32, u32>("-"), Ok((6, 8)));
+
+        let err = second.split_once("-").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.message, "expected \"-\"");
+        assert!(first.split_n::<3>(",").is_err());
+        let err = first.error_at_end("expected more");
+        assert_eq!((err.line, err.column), (1, 8));
+    }
+}
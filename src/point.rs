@@ -0,0 +1,378 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A point on the plane. `y` grows downwards, like rows of puzzle input, so `North` is `-y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A point in space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Point2 {
+        Point2 { x, y }
+    }
+
+    pub fn manhattan(self, other: Point2) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Distance when diagonal steps count as one, so every point around `self` is 1 away.
+    pub fn chebyshev(self, other: Point2) -> i64 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// Each coordinate's sign: -1, 0 or 1.
+    pub fn signum(self) -> Point2 {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+
+    /// One step, possibly diagonal, from `self` towards `target`.
+    pub fn step_toward(self, target: Point2) -> Point2 {
+        self + (target - self).signum()
+    }
+
+    /// A quarter turn clockwise about the origin, as seen with `y` growing downwards.
+    pub fn rotate_cw(self) -> Point2 {
+        Point2::new(-self.y, self.x)
+    }
+
+    /// A quarter turn anticlockwise about the origin, as seen with `y` growing downwards.
+    pub fn rotate_ccw(self) -> Point2 {
+        Point2::new(self.y, -self.x)
+    }
+
+    /// The four points next to `self`, north first and going clockwise.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point2> {
+        Direction::ORTHOGONAL.into_iter().map(move |d| self + d)
+    }
+
+    /// All eight points around `self`, north first and going clockwise.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point2> {
+        Direction::ALL.into_iter().map(move |d| self + d)
+    }
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Point3) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// Distance when diagonal steps count as one, so every point around `self` is 1 away.
+    pub fn chebyshev(self, other: Point3) -> i64 {
+        (self.x - other.x)
+            .abs()
+            .max((self.y - other.y).abs())
+            .max((self.z - other.z).abs())
+    }
+
+    /// Each coordinate's sign: -1, 0 or 1.
+    pub fn signum(self) -> Point3 {
+        Point3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    /// One step, possibly diagonal, from `self` towards `target`.
+    pub fn step_toward(self, target: Point3) -> Point3 {
+        self + (target - self).signum()
+    }
+
+    /// The six points sharing a face with `self`.
+    pub fn neighbours6(self) -> impl Iterator<Item = Point3> {
+        [
+            Point3::new(1, 0, 0),
+            Point3::new(-1, 0, 0),
+            Point3::new(0, 1, 0),
+            Point3::new(0, -1, 0),
+            Point3::new(0, 0, 1),
+            Point3::new(0, 0, -1),
+        ]
+        .into_iter()
+        .map(move |step| self + step)
+    }
+}
+
+/// Implement the arithmetic operators for a point type, coordinate by coordinate.
+macro_rules! point_ops {
+    ($point:ident { $($coord:ident),+ }) => {
+        impl Add for $point {
+            type Output = $point;
+            fn add(self, other: $point) -> $point {
+                $point { $($coord: self.$coord + other.$coord),+ }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $point;
+            fn sub(self, other: $point) -> $point {
+                $point { $($coord: self.$coord - other.$coord),+ }
+            }
+        }
+
+        impl Neg for $point {
+            type Output = $point;
+            fn neg(self) -> $point {
+                $point { $($coord: -self.$coord),+ }
+            }
+        }
+
+        impl Mul<i64> for $point {
+            type Output = $point;
+            fn mul(self, scale: i64) -> $point {
+                $point { $($coord: self.$coord * scale),+ }
+            }
+        }
+
+        impl AddAssign for $point {
+            fn add_assign(&mut self, other: $point) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for $point {
+            fn sub_assign(&mut self, other: $point) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+point_ops!(Point2 { x, y });
+point_ops!(Point3 { x, y, z });
+
+impl From<(i64, i64)> for Point2 {
+    fn from((x, y): (i64, i64)) -> Point2 {
+        Point2::new(x, y)
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Point3 {
+        Point3::new(x, y, z)
+    }
+}
+
+impl fmt::Display for Point2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// `x,y`, as `Display` writes it.
+impl FromStr for Point2 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Point2, String> {
+        let parse = |n: &str| n.trim().parse::<i64>().map_err(|err| err.to_string());
+        match s.split_once(',') {
+            Some((x, y)) => Ok(Point2::new(parse(x)?, parse(y)?)),
+            None => Err(format!("expected x,y, got {:?}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// A compass direction on the plane, diagonals included. North is up, i.e. `-y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// The directions along the axes, clockwise from north.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// A single step this way.
+    pub fn offset(self) -> Point2 {
+        match self {
+            Direction::North => Point2::new(0, -1),
+            Direction::NorthEast => Point2::new(1, -1),
+            Direction::East => Point2::new(1, 0),
+            Direction::SouthEast => Point2::new(1, 1),
+            Direction::South => Point2::new(0, 1),
+            Direction::SouthWest => Point2::new(-1, 1),
+            Direction::West => Point2::new(-1, 0),
+            Direction::NorthWest => Point2::new(-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        let Point2 { x, y } = self.offset();
+        x != 0 && y != 0
+    }
+
+    /// `eighths` eighths of a turn clockwise; negative turns anticlockwise.
+    pub fn rotate(self, eighths: i32) -> Direction {
+        let i = Direction::ALL.iter().position(|&d| d == self).unwrap() as i32;
+        Direction::ALL[(i + eighths).rem_euclid(8) as usize]
+    }
+
+    /// The next direction clockwise, e.g. north to north-east.
+    pub fn clockwise(self) -> Direction {
+        self.rotate(1)
+    }
+
+    /// The next direction anticlockwise, e.g. north to north-west.
+    pub fn anticlockwise(self) -> Direction {
+        self.rotate(-1)
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.rotate(-2)
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(4)
+    }
+
+    /// Read a direction written as `U`/`D`/`L`/`R`, an arrow `^`/`v`/`<`/`>`, or a compass
+    /// point `N`/`S`/`E`/`W`.
+    pub fn from_char(ch: char) -> Result<Direction, String> {
+        match ch {
+            'U' | '^' | 'N' => Ok(Direction::North),
+            'D' | 'v' | 'S' => Ok(Direction::South),
+            'L' | '<' | 'W' => Ok(Direction::West),
+            'R' | '>' | 'E' => Ok(Direction::East),
+            _ => Err(format!(
+                "unknown direction {:?}, expected one of UDLR, ^v<> or NSEW",
+                ch
+            )),
+        }
+    }
+}
+
+/// Accepts anything `from_char` does, plus the diagonals `NE`, `SE`, `SW` and `NW`.
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Direction, String> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(ch), None, None) => Direction::from_char(ch),
+            (Some('N'), Some('E'), None) => Ok(Direction::NorthEast),
+            (Some('S'), Some('E'), None) => Ok(Direction::SouthEast),
+            (Some('S'), Some('W'), None) => Ok(Direction::SouthWest),
+            (Some('N'), Some('W'), None) => Ok(Direction::NorthWest),
+            _ => Err(format!(
+                "unknown direction {:?}, expected one of UDLR, ^v<> or a compass point",
+                s
+            )),
+        }
+    }
+}
+
+impl Add<Direction> for Point2 {
+    type Output = Point2;
+    fn add(self, direction: Direction) -> Point2 {
+        self + direction.offset()
+    }
+}
+
+impl AddAssign<Direction> for Point2 {
+    fn add_assign(&mut self, direction: Direction) {
+        *self = *self + direction;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_arithmetic() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(4, -2);
+        assert_eq!(a + b, Point2::new(5, 0));
+        assert_eq!(a - b, Point2::new(-3, 4));
+        assert_eq!(a * 3, Point2::new(3, 6));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.step_toward(b), Point2::new(2, 1));
+        assert_eq!(a.step_toward(a), a);
+        assert_eq!(a.rotate_cw().rotate_cw(), -a);
+        assert_eq!(a.rotate_cw().rotate_ccw(), a);
+        assert_eq!(
+            Direction::North.offset().rotate_cw(),
+            Direction::East.offset()
+        );
+        assert_eq!(a.neighbours8().filter(|&n| a.chebyshev(n) == 1).count(), 8);
+        assert_eq!(a.to_string().parse(), Ok(a));
+        assert_eq!(" 4, -2".parse(), Ok(b));
+        assert!("4".parse::<Point2>().is_err());
+
+        let p = Point3::new(1, 2, 3);
+        assert_eq!(p - Point3::ORIGIN * 2, p);
+        assert_eq!(p.manhattan(Point3::new(-1, 2, 0)), 5);
+        assert_eq!(p.chebyshev(Point3::new(-1, 2, 0)), 3);
+        assert_eq!(p.neighbours6().map(|n| p.manhattan(n)).sum::<i64>(), 6);
+    }
+
+    #[test]
+    fn test_direction() {
+        for s in ["U", "^", "N"] {
+            assert_eq!(s.parse(), Ok(Direction::North));
+        }
+        assert_eq!("v".parse(), Ok(Direction::South));
+        assert_eq!("L".parse(), Ok(Direction::West));
+        assert_eq!(">".parse(), Ok(Direction::East));
+        assert_eq!("SW".parse(), Ok(Direction::SouthWest));
+        assert!("X".parse::<Direction>().is_err());
+        assert!("NS".parse::<Direction>().is_err());
+
+        assert_eq!(Direction::North.clockwise(), Direction::NorthEast);
+        assert_eq!(Direction::North.anticlockwise(), Direction::NorthWest);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+        assert_eq!(Direction::NorthEast.opposite(), Direction::SouthWest);
+        assert!(Direction::SouthEast.is_diagonal());
+        assert_eq!(Point2::ORIGIN + Direction::NorthWest, Point2::new(-1, -1));
+    }
+}
@@ -0,0 +1,30 @@
+use crate::solution::DynSolution;
+
+// One line per day, kept in order; `aoc new` adds to this list.
+static DAYS: &[&dyn DynSolution] = &[
+    &crate::d01::Day01,
+    &crate::d02::Day02,
+    &crate::d03::Day03,
+    &crate::d04::Day04,
+    &crate::d05::Day05,
+    &crate::d06::Day06,
+    &crate::d07::Day07,
+    &crate::d08::Day08,
+    &crate::d09::Day09,
+    &crate::d10::Day10,
+    &crate::d11::Day11,
+    &crate::d12::Day12,
+    &crate::d13::Day13,
+    &crate::d23::Day23,
+    &crate::d24::Day24,
+    &crate::d25::Day25,
+];
+
+/// Every registered day, in order.
+pub fn all() -> &'static [&'static dyn DynSolution] {
+    DAYS
+}
+
+pub fn get(day: u32) -> Option<&'static dyn DynSolution> {
+    DAYS.iter().find(|solution| solution.day() == day).copied()
+}
@@ -0,0 +1,217 @@
+use crate::input::InputSource;
+use crate::params::Params;
+use crate::registry;
+use crate::solution::{Answer, DynSolution, Part, Unsolved, Witness};
+use crate::trace::{self, Format, Level};
+use serde::Serialize;
+use std::error::Error;
+use std::io::{self, ErrorKind, Write};
+use std::time::Instant;
+
+/// How `run` prints answers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Output {
+    /// `day 01 part 1: 24000`
+    Text,
+    /// One `Record` per line, as JSON.
+    Json,
+}
+
+/// One part's result in `--format json` output.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Record {
+    pub day: u32,
+    pub part: u32,
+    /// `null` if the part failed or isn't solved yet.
+    pub answer: Option<Answer>,
+    /// Seconds spent on the part, not counting parsing.
+    pub elapsed: f64,
+    /// How the answer was found, for days that say.
+    #[serde(skip_serializing_if = "Witness::is_empty")]
+    pub witness: Witness,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Parse the day's input once and print an answer for each requested part, worked out with
+/// `params`, followed by its witness if `explain` is set. A failing part is reported, on stderr
+/// for text output, without stopping the others. JSON output always has the witness. Returns how
+/// many parts failed; a part that isn't solved yet doesn't count.
+pub fn run(
+    solution: &dyn DynSolution,
+    input: &InputSource,
+    params: Params,
+    parts: &[Part],
+    output: Output,
+    explain: bool,
+) -> Result<usize, Box<dyn Error>> {
+    let day = solution.day();
+    let content = input.read()?;
+    let parsed = solution.parse_with(&content, params)?;
+    let mut stdout = io::stdout().lock();
+    let mut failed = 0;
+    for &part in parts {
+        let start = Instant::now();
+        let result = parsed.explain(part);
+        let elapsed = start.elapsed().as_secs_f64();
+        if matches!(&result, Err(err) if !err.is::<Unsolved>()) {
+            failed += 1;
+        }
+        match output {
+            Output::Text => match result {
+                Ok(explained) => {
+                    writeln!(stdout, "day {:02} part {}: {}", day, part, explained.answer)?;
+                    if explain {
+                        for line in explained.witness.to_string().lines() {
+                            writeln!(stdout, "    {}", line)?;
+                        }
+                    }
+                }
+                Err(err) if err.is::<Unsolved>() => {
+                    writeln!(stdout, "day {:02} part {}: {}", day, part, err)?
+                }
+                Err(err) => eprintln!("day {:02} part {}: error: {}", day, part, err),
+            },
+            Output::Json => {
+                let (answer, witness, error) = match result {
+                    Ok(explained) => (Some(explained.answer), explained.witness, None),
+                    Err(err) => (None, Witness::new(), Some(err.to_string())),
+                };
+                let record = Record {
+                    day,
+                    part: part.number(),
+                    answer,
+                    elapsed,
+                    witness,
+                    error,
+                };
+                writeln!(stdout, "{}", serde_json::to_string(&record)?)?;
+            }
+        }
+    }
+    Ok(failed)
+}
+
+/// An error for `failed` parts, if there were any.
+pub fn parts_failed(failed: usize) -> Result<(), Box<dyn Error>> {
+    match failed {
+        0 => Ok(()),
+        1 => Err("1 part failed")?,
+        n => Err(format!("{} parts failed", n))?,
+    }
+}
+
+/// Whether `err` is from writing to a pipe whose reader has gone, like `head` once it has all the
+/// lines it wants. That's a reason to stop rather than a failure.
+pub fn is_broken_pipe(err: &(dyn Error + 'static)) -> bool {
+    err.downcast_ref::<io::Error>()
+        .is_some_and(|err| err.kind() == ErrorKind::BrokenPipe)
+}
+
+/// Print a failed command's error and exit non-zero. Errors are printed with `Display` rather
+/// than the `Debug` that returning them from `main` would use, so input errors show their snippet.
+/// A broken pipe just ends the command.
+pub fn exit_on_error(result: Result<(), Box<dyn Error>>) {
+    if let Err(err) = result {
+        if is_broken_pipe(err.as_ref()) {
+            return;
+        }
+        eprintln!("error: {}", err);
+        std::process::exit(1);
+    }
+}
+
+/// Take the tracing flags out of `args` and start tracing to match: `-v` for debug events, `-vv`
+/// (or `-v -v`) for every step as well, and `--trace-json` to write them as JSON lines.
+pub fn init_tracing(args: &[String]) -> Vec<String> {
+    let mut verbosity = 0;
+    let mut format = Format::Text;
+    let mut rest = vec![];
+    for arg in args {
+        match arg.as_str() {
+            "-v" => verbosity += 1,
+            "-vv" => verbosity += 2,
+            "--trace-json" => format = Format::Json,
+            _ => rest.push(arg.clone()),
+        }
+    }
+    trace::init(Level::from_verbosity(verbosity), format);
+    rest
+}
+
+/// Entry point for the per-day binaries:
+/// `dNN [-v|-vv] [--trace-json] [--explain] [--input <path>|-]`.
+pub fn day_main(day: u32) {
+    exit_on_error(run_day(day));
+}
+
+fn run_day(day: u32) -> Result<(), Box<dyn Error>> {
+    let usage = format!(
+        "usage: d{:02} [-v|-vv] [--trace-json] [--explain] [--input <path>|-]",
+        day
+    );
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut args = init_tracing(&args);
+    let explain = args.iter().any(|arg| arg == "--explain");
+    args.retain(|arg| arg != "--explain");
+    let input = match args.as_slice() {
+        [] => None,
+        [flag, path] if flag == "--input" => Some(path.as_str()),
+        _ => return Err(usage)?,
+    };
+
+    let solution = registry::get(day).ok_or(format!("day {} is not registered", day))?;
+    let failed = run(
+        solution,
+        &InputSource::for_day(day, input),
+        Params::defaults(solution.params()),
+        &Part::BOTH,
+        Output::Text,
+        explain,
+    )?;
+    parts_failed(failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_json() {
+        let record = Record {
+            day: 10,
+            part: 2,
+            answer: Some(Answer::from("#.\n.#")),
+            elapsed: 0.5,
+            witness: Witness::new(),
+            error: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r##"{"day":10,"part":2,"answer":"#.\n.#","elapsed":0.5}"##
+        );
+        let record = Record {
+            day: 1,
+            part: 1,
+            answer: Some(Answer::from(24000usize)),
+            elapsed: 0.0,
+            witness: Witness::new().with("elf", 4).with("calories", [24000]),
+            error: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            concat!(
+                r#"{"day":1,"part":1,"answer":24000,"elapsed":0.0,"#,
+                r#""witness":{"elf":4,"calories":[24000]}}"#
+            )
+        );
+        assert_eq!(record.witness.to_string(), "elf: 4\ncalories: [24000]\n");
+
+        let answer = Answer::from(u64::MAX);
+        assert_eq!(answer.to_string(), "18446744073709551615");
+        assert_eq!(
+            serde_json::to_string(&answer).unwrap(),
+            "18446744073709551615"
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MOD_TEMPLATE: &str = "use crate::params::Params;
+use crate::solution::{Solution, Unsolved};
+use std::error::Error;
+
+pub struct Day{NN};
+
+impl Solution for Day{NN} {
+    const DAY: u32 = {DAY};
+
+    type Input = Vec<String>;
+    type Answer1 = Unsolved;
+    type Answer2 = Unsolved;
+
+    fn parse(content: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(content.lines().map(String::from).collect())
+    }
+
+    fn part1(_: &Self::Input, _: &Params) -> Result<Unsolved, Box<dyn Error>> {
+        Err(Unsolved)?
+    }
+
+    fn part2(_: &Self::Input, _: &Params) -> Result<Unsolved, Box<dyn Error>> {
+        Err(Unsolved)?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // paste the puzzle's example into `example`, put its answers here and drop the `#[ignore]`
+    crate::examples::example_tests!(#[ignore] Day{NN}, \"\", \"\");
+}
+";
+
+const MAIN_TEMPLATE: &str = "fn main() {
+    advent_of_code_2022::runner::day_main({DAY});
+}
+";
+
+fn render(template: &str, day: u32) -> String {
+    template
+        .replace("{NN}", &format!("{:02}", day))
+        .replace("{DAY}", &day.to_string())
+}
+
+/// Insert `entry` into the run of lines for which `is_entry` holds, keeping the run sorted.
+fn insert_line(content: &str, entry: &str, is_entry: fn(&str) -> bool) -> Option<String> {
+    let mut lines: Vec<&str> = content.lines().collect();
+    let last = lines.iter().rposition(|line| is_entry(line))?;
+    let at = lines
+        .iter()
+        .position(|line| is_entry(line) && *line > entry)
+        .unwrap_or(last + 1);
+    lines.insert(at, entry);
+    Some(lines.join("\n") + "\n")
+}
+
+/// Insert a blank-line separated `block` into Cargo.toml's day binaries, keeping them sorted.
+fn insert_bin(manifest: &str, block: &str) -> Option<String> {
+    let is_day_bin = |b: &str| b.starts_with("[[bin]]\nname = \"d");
+    let mut blocks: Vec<&str> = manifest.trim_end().split("\n\n").collect();
+    let last = blocks.iter().rposition(|b| is_day_bin(b))?;
+    let at = blocks
+        .iter()
+        .position(|b| is_day_bin(b) && *b > block)
+        .unwrap_or(last + 1);
+    blocks.insert(at, block);
+    Some(blocks.join("\n\n") + "\n")
+}
+
+/// Create `src/dNN` for a new day from the template, with empty `input` and `example`
+/// placeholders, and register it in `src/lib.rs`, `src/registry.rs` and `Cargo.toml`. Returns
+/// every path created or changed.
+pub fn new_day(root: &Path, day: u32) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    if !(1..=25).contains(&day) {
+        Err(format!("there is no day {}", day))?;
+    }
+    let nn = format!("d{:02}", day);
+    let dir = root.join("src").join(&nn);
+    if dir.exists() {
+        Err(format!("{} already exists", dir.display()))?;
+    }
+
+    let is_mod = |line: &str| line.starts_with("pub mod d");
+    let is_registered = |line: &str| line.starts_with("    &crate::d");
+    let edits = [
+        (
+            root.join("src/lib.rs"),
+            format!("pub mod {};", nn),
+            is_mod as fn(&str) -> bool,
+        ),
+        (
+            root.join("src/registry.rs"),
+            format!("    &crate::{}::Day{:02},", nn, day),
+            is_registered,
+        ),
+    ];
+    // work out every change before writing anything, so a file we don't recognise leaves the
+    // tree untouched
+    let mut writes: Vec<(PathBuf, String)> = vec![];
+    for (path, entry, is_entry) in edits {
+        let content = fs::read_to_string(&path)
+            .map_err(|err| format!("could not read {}: {}", path.display(), err))?;
+        let updated = insert_line(&content, &entry, is_entry).ok_or(format!(
+            "could not find where to add {} in {}",
+            nn,
+            path.display()
+        ))?;
+        writes.push((path, updated));
+    }
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = fs::read_to_string(&manifest_path)
+        .map_err(|err| format!("could not read {}: {}", manifest_path.display(), err))?;
+    let block = format!("[[bin]]\nname = \"{}\"\npath = \"src/{}/main.rs\"", nn, nn);
+    let manifest =
+        insert_bin(&manifest, &block).ok_or("could not find the day binaries in Cargo.toml")?;
+    writes.push((manifest_path, manifest));
+
+    fs::create_dir_all(&dir)?;
+    writes.push((dir.join("mod.rs"), render(MOD_TEMPLATE, day)));
+    writes.push((dir.join("main.rs"), render(MAIN_TEMPLATE, day)));
+    writes.push((dir.join("input"), String::new()));
+    writes.push((dir.join("example"), String::new()));
+
+    let mut touched = vec![];
+    for (path, content) in writes {
+        fs::write(&path, content)
+            .map_err(|err| format!("could not write {}: {}", path.display(), err))?;
+        touched.push(path);
+    }
+    Ok(touched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-new-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/d13")).unwrap();
+        fs::write(
+            root.join("src/lib.rs"),
+            "pub mod solution;\n\npub mod d01;\npub mod d13;\npub mod d23;\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("src/registry.rs"),
+            "static DAYS: &[&dyn DynSolution] = &[\n    &crate::d01::Day01,\n    &crate::d13::Day13,\n];\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[lib]\npath = \"src/lib.rs\"\n\n[[bin]]\nname = \"aoc\"\npath = \"src/aoc/main.rs\"\n\n[[bin]]\nname = \"d13\"\npath = \"src/d13/main.rs\"\n\n[[bin]]\nname = \"d23\"\npath = \"src/d23/main.rs\"\n\n[[test]]\nname = \"answers\"\n",
+        )
+        .unwrap();
+
+        new_day(&root, 14).unwrap();
+        let read = |path: &str| fs::read_to_string(root.join(path)).unwrap();
+        assert_eq!(
+            read("src/lib.rs"),
+            "pub mod solution;\n\npub mod d01;\npub mod d13;\npub mod d14;\npub mod d23;\n"
+        );
+        assert_eq!(
+            read("src/registry.rs"),
+            "static DAYS: &[&dyn DynSolution] = &[\n    &crate::d01::Day01,\n    &crate::d13::Day13,\n    &crate::d14::Day14,\n];\n"
+        );
+        assert!(read("Cargo.toml").contains(
+            "name = \"d13\"\npath = \"src/d13/main.rs\"\n\n[[bin]]\nname = \"d14\"\npath = \"src/d14/main.rs\"\n\n[[bin]]\nname = \"d23\""
+        ));
+        assert!(read("src/d14/mod.rs").contains("pub struct Day14;\n"));
+        assert!(read("src/d14/main.rs").contains("day_main(14)"));
+        assert!(read("src/d14/mod.rs")
+            .contains("crate::examples::example_tests!(#[ignore] Day14, \"\", \"\");\n"));
+        assert_eq!(read("src/d14/input"), "");
+        assert_eq!(read("src/d14/example"), "");
+
+        assert!(new_day(&root, 14).is_err());
+        assert!(new_day(&root, 13).is_err());
+        assert!(new_day(&root, 26).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
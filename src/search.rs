@@ -0,0 +1,426 @@
+//! Searches over implicit graphs, where a closure gives each node's neighbours.
+use crate::dot::{Dot, ToDot};
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+use std::hash::Hash;
+use std::rc::Rc;
+
+/// The cost of an edge or a path in a weighted search.
+pub type Cost = u64;
+
+/// A node reached by `bfs`, linked back through the node it was first reached from.
+#[derive(Clone)]
+pub struct BfsNode<NodeType> {
+    pub val: NodeType,
+    pub parent: Option<Rc<BfsNode<NodeType>>>,
+    /// Steps from the nearest start.
+    pub depth: usize,
+}
+
+impl<NodeType: Clone> BfsNode<NodeType> {
+    /// The nodes from a start to this one, both included.
+    pub fn path(&self) -> Vec<NodeType> {
+        let mut path = vec![self.val.clone()];
+        let mut node = self.parent.clone();
+        while let Some(parent) = node {
+            path.push(parent.val.clone());
+            node = parent.parent.clone();
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Unlinks the chain of parents one at a time, since dropping a long path recursively would
+/// overflow the stack.
+impl<NodeType> Drop for BfsNode<NodeType> {
+    fn drop(&mut self) {
+        let mut parent = self.parent.take();
+        while let Some(node) = parent {
+            match Rc::try_unwrap(node) {
+                Ok(mut node) => parent = node.parent.take(),
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+/// Nodes in the order a breadth-first search reaches them, each only once.
+pub struct BfsIter<NodeType, C> {
+    queue: VecDeque<Rc<BfsNode<NodeType>>>,
+    get_children: C,
+    visited: HashSet<NodeType>,
+}
+
+impl<NodeType, C, I> Iterator for BfsIter<NodeType, C>
+where
+    C: FnMut(&NodeType) -> I,
+    I: IntoIterator<Item = NodeType>,
+    NodeType: Clone + Hash + Eq,
+{
+    type Item = Rc<BfsNode<NodeType>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let head = self.queue.pop_front()?;
+        for child in (self.get_children)(&head.val) {
+            if self.visited.insert(child.clone()) {
+                self.queue.push_back(Rc::new(BfsNode {
+                    val: child,
+                    parent: Some(head.clone()),
+                    depth: head.depth + 1,
+                }));
+            }
+        }
+        Some(head)
+    }
+}
+
+pub fn bfs<NodeType, C, I>(startnode: NodeType, get_children: C) -> BfsIter<NodeType, C>
+where
+    C: FnMut(&NodeType) -> I,
+    I: IntoIterator<Item = NodeType>,
+    NodeType: Clone + Hash + Eq,
+{
+    bfs_from([startnode], get_children)
+}
+
+/// A breadth-first search from all of `starts` at once, so each node's depth is its distance
+/// from the nearest of them.
+pub fn bfs_from<NodeType, C, I>(
+    starts: impl IntoIterator<Item = NodeType>,
+    get_children: C,
+) -> BfsIter<NodeType, C>
+where
+    C: FnMut(&NodeType) -> I,
+    I: IntoIterator<Item = NodeType>,
+    NodeType: Clone + Hash + Eq,
+{
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if visited.insert(start.clone()) {
+            queue.push_back(Rc::new(BfsNode {
+                val: start,
+                parent: None,
+                depth: 0,
+            }));
+        }
+    }
+    return BfsIter {
+        queue,
+        get_children,
+        visited,
+    };
+}
+
+/// Every node a breadth-first search reached, in the order it reached them, each linked to the
+/// node it was first reached from. Collect a `bfs` into one to draw the search with `ToDot`.
+pub struct BfsTree<NodeType> {
+    pub nodes: Vec<Rc<BfsNode<NodeType>>>,
+}
+
+impl<NodeType> FromIterator<Rc<BfsNode<NodeType>>> for BfsTree<NodeType> {
+    fn from_iter<T: IntoIterator<Item = Rc<BfsNode<NodeType>>>>(iter: T) -> Self {
+        BfsTree {
+            nodes: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Nodes labelled with their distance from the nearest start, with an edge from each node's
+/// parent.
+impl<NodeType: Display> ToDot for BfsTree<NodeType> {
+    fn to_dot(&self) -> Dot {
+        let mut dot = Dot::new("bfs");
+        for node in &self.nodes {
+            let label = format!("{}\n{}", node.val, node.depth);
+            dot.node(&node.val, &[("label", label)]);
+            if let Some(parent) = &node.parent {
+                dot.edge(&parent.val, &node.val, &[]);
+            }
+        }
+        dot
+    }
+}
+
+/// The distance from the nearest of `starts` to every node that can be reached.
+pub fn bfs_distances<NodeType, C, I>(
+    starts: impl IntoIterator<Item = NodeType>,
+    get_children: C,
+) -> HashMap<NodeType, usize>
+where
+    C: FnMut(&NodeType) -> I,
+    I: IntoIterator<Item = NodeType>,
+    NodeType: Clone + Hash + Eq,
+{
+    bfs_from(starts, get_children)
+        .map(|node| (node.val.clone(), node.depth))
+        .collect()
+}
+
+/// The cheapest way found to a goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found<NodeType> {
+    pub cost: Cost,
+    /// From the start to the goal, both included.
+    pub path: Vec<NodeType>,
+}
+
+/// The cheapest cost from the nearest start to every node reached by a weighted search, with how
+/// each was reached.
+#[derive(Debug, Clone)]
+pub struct Distances<NodeType> {
+    pub costs: HashMap<NodeType, Cost>,
+    parents: HashMap<NodeType, NodeType>,
+}
+
+impl<NodeType: Clone + Hash + Eq> Distances<NodeType> {
+    pub fn cost(&self, node: &NodeType) -> Option<Cost> {
+        self.costs.get(node).copied()
+    }
+
+    /// The cheapest path from a start to `to`, both included, if `to` was reached.
+    pub fn path(&self, to: &NodeType) -> Option<Vec<NodeType>> {
+        self.costs.get(to)?;
+        let mut path = vec![to.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// The shared loop behind the weighted searches: expand the node with the lowest cost plus
+/// `heuristic`, stopping at the first goal.
+fn best_first<NodeType, E, I, H, G>(
+    starts: impl IntoIterator<Item = NodeType>,
+    mut edges: E,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> (Distances<NodeType>, Option<NodeType>)
+where
+    E: FnMut(&NodeType) -> I,
+    I: IntoIterator<Item = (NodeType, Cost)>,
+    H: FnMut(&NodeType) -> Cost,
+    G: FnMut(&NodeType) -> bool,
+    NodeType: Clone + Hash + Eq,
+{
+    let mut costs: HashMap<NodeType, Cost> = HashMap::new();
+    let mut parents: HashMap<NodeType, NodeType> = HashMap::new();
+    let mut done: HashSet<NodeType> = HashSet::new();
+    // nodes are kept in a side table so the heap only needs to order numbers
+    let mut nodes: Vec<NodeType> = vec![];
+    let mut heap: BinaryHeap<Reverse<(Cost, Cost, usize)>> = BinaryHeap::new();
+
+    for start in starts {
+        if costs.insert(start.clone(), 0).is_none() {
+            heap.push(Reverse((heuristic(&start), 0, nodes.len())));
+            nodes.push(start);
+        }
+    }
+
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        let node = nodes[i].clone();
+        if costs[&node] < cost || !done.insert(node.clone()) {
+            continue;
+        }
+        if is_goal(&node) {
+            return (Distances { costs, parents }, Some(node));
+        }
+        for (next, weight) in edges(&node) {
+            let next_cost = cost + weight;
+            match costs.entry(next.clone()) {
+                Entry::Occupied(entry) if *entry.get() <= next_cost => continue,
+                Entry::Occupied(mut entry) => {
+                    entry.insert(next_cost);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(next_cost);
+                }
+            }
+            parents.insert(next.clone(), node.clone());
+            // a heuristic that's admissible but not consistent can lead to a cheaper way to a
+            // node that's already been expanded, so it gets expanded again
+            done.remove(&next);
+            heap.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                nodes.len(),
+            )));
+            nodes.push(next);
+        }
+    }
+    (Distances { costs, parents }, None)
+}
+
+/// The cheapest path from any of `starts` to a node satisfying `is_goal`, where `edges` gives
+/// each neighbour of a node with the cost of stepping to it.
+pub fn dijkstra<NodeType, E, I, G>(
+    starts: impl IntoIterator<Item = NodeType>,
+    edges: E,
+    is_goal: G,
+) -> Option<Found<NodeType>>
+where
+    E: FnMut(&NodeType) -> I,
+    I: IntoIterator<Item = (NodeType, Cost)>,
+    G: FnMut(&NodeType) -> bool,
+    NodeType: Clone + Hash + Eq,
+{
+    astar(starts, edges, |_| 0, is_goal)
+}
+
+/// Like `dijkstra`, but guided by `heuristic`, an estimate of the cost left to a goal. The path
+/// found is the cheapest as long as the estimate never overshoots. An estimate that's also
+/// consistent, never dropping by more than the cost of an edge, means no node is expanded twice.
+pub fn astar<NodeType, E, I, H, G>(
+    starts: impl IntoIterator<Item = NodeType>,
+    edges: E,
+    heuristic: H,
+    is_goal: G,
+) -> Option<Found<NodeType>>
+where
+    E: FnMut(&NodeType) -> I,
+    I: IntoIterator<Item = (NodeType, Cost)>,
+    H: FnMut(&NodeType) -> Cost,
+    G: FnMut(&NodeType) -> bool,
+    NodeType: Clone + Hash + Eq,
+{
+    let (distances, goal) = best_first(starts, edges, heuristic, is_goal);
+    let goal = goal?;
+    Some(Found {
+        cost: distances.costs[&goal],
+        path: distances.path(&goal).unwrap(),
+    })
+}
+
+/// The cheapest cost from the nearest of `starts` to everything reachable.
+pub fn dijkstra_distances<NodeType, E, I>(
+    starts: impl IntoIterator<Item = NodeType>,
+    edges: E,
+) -> Distances<NodeType>
+where
+    E: FnMut(&NodeType) -> I,
+    I: IntoIterator<Item = (NodeType, Cost)>,
+    NodeType: Clone + Hash + Eq,
+{
+    best_first(starts, edges, |_| 0, |_| false).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple() {
+        // i guess this doesn't actually test anything. It just runs the code and makes sure it
+        // doesn't panic or endless loop
+        let iter = bfs(1, |&x| {
+            if x < 10 {
+                vec![2 * x, 2 * x + 1]
+            } else {
+                vec![]
+            }
+        });
+        assert_eq!(
+            iter.map(|x| x.val).collect::<Vec<_>>(),
+            (1..=19).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_does_not_revisit_parent() {
+        // cyclc grooup
+        let iter = bfs(0, |&x| vec![(x + 1) % 5]);
+        let x: Vec<i32> = iter.map(|x| x.val).take(10).collect();
+        assert_eq!(x, (0..5).collect::<Vec<_>>());
+
+        // grid from (0, 0) to (4, 4)
+        let n = 5;
+        let iter = bfs((0, 0), |&(r, c)| {
+            vec![(r + 1, c), (r, c + 1)]
+                .into_iter()
+                .filter(|&(r, c)| r < n && c < n)
+                .collect::<Vec<_>>()
+        });
+        let mut result = iter.map(|x| x.val).collect::<Vec<_>>();
+        result.sort();
+        assert_eq!(result, {
+            let mut tuples = Vec::new();
+            for i in 0..n {
+                for j in 0..n {
+                    tuples.push((i, j));
+                }
+            }
+            tuples.sort();
+            tuples
+        });
+    }
+
+    #[test]
+    fn test_bfs_paths_and_many_starts() {
+        // more nodes than the old fixed size queue could hold at once
+        let line = |&x: &i32| {
+            [x - 1, x + 1]
+                .into_iter()
+                .filter(|x| (0..50000).contains(x))
+        };
+        let node = bfs(0, line).find(|node| node.val == 4).unwrap();
+        assert_eq!(node.depth, 4);
+        assert_eq!(node.path(), vec![0, 1, 2, 3, 4]);
+        assert_eq!(bfs_from(0..50000, line).count(), 50000);
+
+        let distances = bfs_distances([0, 10], line);
+        assert_eq!(distances[&4], 4);
+        assert_eq!(distances[&7], 3);
+        assert_eq!(distances[&20], 10);
+    }
+
+    #[test]
+    fn test_weighted() {
+        // going 0 -> 1 -> 2 -> 3 costs 3, skipping straight to 3 costs 5
+        let edges = |&x: &u32| -> Vec<(u32, Cost)> {
+            match x {
+                0 => vec![(1, 1), (3, 5)],
+                1 => vec![(2, 1)],
+                2 => vec![(3, 1)],
+                _ => vec![],
+            }
+        };
+        let found = dijkstra([0], edges, |&x| x == 3).unwrap();
+        assert_eq!(found.cost, 3);
+        assert_eq!(found.path, vec![0, 1, 2, 3]);
+        assert_eq!(
+            astar([0], edges, |&x| 3 - x as Cost, |&x| x == 3),
+            Some(found)
+        );
+        assert_eq!(dijkstra([1], edges, |&x| x == 0), None);
+
+        let distances = dijkstra_distances([0], edges);
+        assert_eq!(distances.cost(&2), Some(2));
+        assert_eq!(distances.path(&3), Some(vec![0, 1, 2, 3]));
+        assert_eq!(distances.path(&7), None);
+    }
+
+    #[test]
+    fn test_astar_inconsistent_heuristic() {
+        // 0 -> 1 -> 3 -> 4 costs 7, and 0 -> 2 -> 3 -> 4 costs 9
+        let edges = |&x: &u32| -> Vec<(u32, Cost)> {
+            match x {
+                0 => vec![(1, 1), (2, 1)],
+                1 => vec![(3, 1)],
+                2 => vec![(3, 3)],
+                3 => vec![(4, 5)],
+                _ => vec![],
+            }
+        };
+        // never more than the real cost left, but 1 looks much worse than 3 behind it, so 3 is
+        // first expanded by way of 2
+        let heuristic = |&x: &u32| if x == 1 { 6 } else { 0 };
+        let found = astar([0], edges, heuristic, |&x| x == 4).unwrap();
+        assert_eq!(found.cost, 7);
+        assert_eq!(found.path, vec![0, 1, 3, 4]);
+    }
+}
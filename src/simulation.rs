@@ -0,0 +1,184 @@
+//! A common shape for puzzles that step a state forward until something happens, with one driver
+//! to run them.
+use std::error::Error;
+use std::fmt;
+
+/// Something that advances one step at a time.
+pub trait Simulation {
+    /// What's worth keeping of the state after a step, e.g. to draw it or check it later.
+    type Snapshot;
+
+    fn step(&mut self);
+
+    /// Whether stepping any further means nothing, e.g. the input ran out. Simulations that go on
+    /// forever never are.
+    fn is_done(&self) -> bool {
+        false
+    }
+
+    fn snapshot(&self) -> Self::Snapshot;
+}
+
+/// The run went past the driver's step limit without finishing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TooManySteps {
+    pub max_steps: usize,
+}
+
+impl fmt::Display for TooManySteps {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "gave up after {} steps", self.max_steps)
+    }
+}
+
+impl Error for TooManySteps {}
+
+type Observer<'a, S> = Box<dyn FnMut(usize, &S) + 'a>;
+
+/// Runs a simulation, counting steps, calling observers after each one and refusing to go past
+/// an optional limit.
+pub struct Driver<'a, S: Simulation> {
+    sim: S,
+    steps: usize,
+    max_steps: Option<usize>,
+    observers: Vec<Observer<'a, S>>,
+}
+
+impl<'a, S: Simulation> Driver<'a, S> {
+    pub fn new(sim: S) -> Driver<'a, S> {
+        Driver {
+            sim,
+            steps: 0,
+            max_steps: None,
+            observers: vec![],
+        }
+    }
+
+    /// Fail with `TooManySteps` rather than take more than `max_steps` steps in all.
+    pub fn max_steps(mut self, max_steps: usize) -> Driver<'a, S> {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// Call `observer` after every step with the number of steps taken so far and the state.
+    pub fn observe(mut self, observer: impl FnMut(usize, &S) + 'a) -> Driver<'a, S> {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    pub fn sim(&self) -> &S {
+        &self.sim
+    }
+
+    pub fn into_inner(self) -> S {
+        self.sim
+    }
+
+    /// Steps taken so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Take one step, unless the simulation is done. Returns whether a step was taken.
+    pub fn step(&mut self) -> Result<bool, TooManySteps> {
+        if self.sim.is_done() {
+            return Ok(false);
+        }
+        if let Some(max_steps) = self.max_steps.filter(|&max| self.steps >= max) {
+            return Err(TooManySteps { max_steps });
+        }
+        self.sim.step();
+        self.steps += 1;
+        for observer in self.observers.iter_mut() {
+            observer(self.steps, &self.sim);
+        }
+        Ok(true)
+    }
+
+    /// Take up to `n` steps, stopping early if the simulation is done. Returns how many were
+    /// taken.
+    pub fn run(&mut self, n: usize) -> Result<usize, TooManySteps> {
+        for taken in 0..n {
+            if !self.step()? {
+                return Ok(taken);
+            }
+        }
+        Ok(n)
+    }
+
+    /// Step until `pred` holds for the state, checking before each step. Returns false if the
+    /// simulation was done first.
+    pub fn run_until(&mut self, mut pred: impl FnMut(&S) -> bool) -> Result<bool, TooManySteps> {
+        loop {
+            if pred(&self.sim) {
+                return Ok(true);
+            }
+            if !self.step()? {
+                return Ok(false);
+            }
+        }
+    }
+
+    /// Step until the simulation is done. Returns the number of steps taken in all.
+    pub fn run_to_end(&mut self) -> Result<usize, TooManySteps> {
+        while self.step()? {}
+        Ok(self.steps)
+    }
+
+    /// The snapshot after each step until the simulation is done.
+    pub fn snapshots(&mut self) -> Result<Vec<S::Snapshot>, TooManySteps> {
+        let mut snapshots = vec![];
+        while self.step()? {
+            snapshots.push(self.sim.snapshot());
+        }
+        Ok(snapshots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up to `end`.
+    struct Counter {
+        n: u32,
+        end: u32,
+    }
+
+    impl Simulation for Counter {
+        type Snapshot = u32;
+
+        fn step(&mut self) {
+            self.n += 1;
+        }
+
+        fn is_done(&self) -> bool {
+            self.n == self.end
+        }
+
+        fn snapshot(&self) -> u32 {
+            self.n
+        }
+    }
+
+    #[test]
+    fn test_driver() {
+        let mut seen = vec![];
+        let mut driver = Driver::new(Counter { n: 0, end: 10 }).observe(|step, counter| {
+            seen.push((step, counter.n));
+        });
+        assert_eq!(driver.run(3), Ok(3));
+        assert_eq!(driver.run_until(|counter| counter.n == 5), Ok(true));
+        assert_eq!(driver.snapshots(), Ok(vec![6, 7, 8, 9, 10]));
+        assert_eq!(driver.run(3), Ok(0));
+        assert_eq!(driver.run_until(|counter| counter.n == 20), Ok(false));
+        assert_eq!(driver.steps(), 10);
+        drop(driver);
+        assert_eq!(seen[..2], [(1, 1), (2, 2)]);
+        assert_eq!(seen.len(), 10);
+
+        let mut driver = Driver::new(Counter { n: 0, end: 10 }).max_steps(4);
+        assert_eq!(driver.run_to_end(), Err(TooManySteps { max_steps: 4 }));
+        assert_eq!(driver.sim().n, 4);
+    }
+}
@@ -0,0 +1,314 @@
+use crate::animate::Animator;
+use crate::dot::Dot;
+use crate::error::AocError;
+use crate::image::Image;
+use crate::params::{Param, Params};
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use serde_json::{Number, Value};
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+
+/// A day's puzzle. The input is parsed once into `Input`, then both parts are answered from it.
+pub trait Solution {
+    const DAY: u32;
+    /// Numbers the puzzle picks that can be changed with `--set`, looked up in the `Params`
+    /// each part is handed.
+    const PARAMS: &'static [Param] = &[];
+
+    type Input;
+    /// A number or text, or either wrapped in `Explained` to say how it was found.
+    type Answer1: IntoExplained;
+    type Answer2: IntoExplained;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>>;
+    fn part1(input: &Self::Input, params: &Params) -> Result<Self::Answer1, Box<dyn Error>>;
+    fn part2(input: &Self::Input, params: &Params) -> Result<Self::Answer2, Box<dyn Error>>;
+
+    /// Show `part` being worked out, a frame at a time. Most days have nothing worth watching.
+    fn animate(
+        _input: &Self::Input,
+        _params: &Params,
+        _part: Part,
+        _animator: &mut Animator,
+    ) -> Result<(), Box<dyn Error>> {
+        Err(format!("day {} has no animation", Self::DAY))?
+    }
+
+    /// Draw `part` as a picture, or a frame per step for a simulation. Most days have nothing
+    /// worth drawing.
+    fn images(
+        _input: &Self::Input,
+        _params: &Params,
+        _part: Part,
+    ) -> Result<Vec<Image>, Box<dyn Error>> {
+        Err(format!("day {} has no images", Self::DAY))?
+    }
+
+    /// The model behind `part` as a graph, for days whose input is a tree or a network.
+    fn graph(_input: &Self::Input, _params: &Params, _part: Part) -> Result<Dot, Box<dyn Error>> {
+        Err(format!("day {} has no graph", Self::DAY))?
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A part's answer with the day's own type erased. Numbers are kept as numbers so that JSON output
+/// doesn't quote them, whatever integer type they came from; everything else, like d10's picture of the screen, is text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(Number),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! number_answers {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Number(n.into())
+                }
+            }
+        )*
+    };
+}
+
+number_answers!(i32, u32, i64, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// Why an answer is what it is: the path that was found, the directory that was picked, the
+/// items that added up to it. Fields keep the order they were added in, in text and in JSON.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Witness {
+    fields: Vec<(String, Value)>,
+}
+
+impl Witness {
+    pub fn new() -> Witness {
+        Witness::default()
+    }
+
+    /// The witness with `value`, anything serde can write as JSON, added as `key`.
+    pub fn with(mut self, key: &str, value: impl Serialize) -> Witness {
+        let value = serde_json::to_value(value).expect("witness values must be valid JSON");
+        self.fields.push((key.to_string(), value));
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == key)
+            .map(|(_, value)| value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+impl Serialize for Witness {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.fields.len()))?;
+        for (key, value) in &self.fields {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// One `key: value` line per field, with strings unquoted.
+impl Display for Witness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (key, value) in &self.fields {
+            match value {
+                Value::String(s) => writeln!(f, "{}: {}", key, s)?,
+                _ => writeln!(f, "{}: {}", key, value)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An answer along with its witness.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explained<T> {
+    pub answer: T,
+    pub witness: Witness,
+}
+
+impl<T> Explained<T> {
+    pub fn new(answer: T, witness: Witness) -> Explained<T> {
+        Explained { answer, witness }
+    }
+}
+
+/// A part's answer, explained or not, with the day's own type erased.
+pub trait IntoExplained {
+    fn into_explained(self) -> Explained<Answer>;
+}
+
+impl<T: Into<Answer>> IntoExplained for T {
+    fn into_explained(self) -> Explained<Answer> {
+        Explained::new(self.into(), Witness::new())
+    }
+}
+
+impl<T: Into<Answer>> IntoExplained for Explained<T> {
+    fn into_explained(self) -> Explained<Answer> {
+        Explained::new(self.answer.into(), self.witness)
+    }
+}
+
+/// Answer type (and error) for parts that haven't been solved yet.
+#[derive(Debug)]
+pub struct Unsolved;
+
+impl Display for Unsolved {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "not implemented")
+    }
+}
+
+impl Error for Unsolved {}
+
+impl From<Unsolved> for Answer {
+    fn from(unsolved: Unsolved) -> Answer {
+        Answer::Text(unsolved.to_string())
+    }
+}
+
+/// Object safe view of a `Solution` so that days with different input and answer types can live
+/// in the same registry.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u32;
+    fn params(&self) -> &'static [Param];
+
+    /// Parse `input` for parts that will use `params`.
+    fn parse_with(
+        &self,
+        input: &str,
+        params: Params,
+    ) -> Result<Box<dyn ParsedInput>, Box<dyn Error>>;
+
+    /// Parse `input` for parts that will use the day's default parameters.
+    fn parse(&self, input: &str) -> Result<Box<dyn ParsedInput>, Box<dyn Error>> {
+        self.parse_with(input, Params::defaults(self.params()))
+    }
+}
+
+/// A parsed input, along with the parameters to use, that can answer either part.
+pub trait ParsedInput {
+    /// The part's answer with its witness, empty for days that don't explain themselves.
+    fn explain(&self, part: Part) -> Result<Explained<Answer>, Box<dyn Error>>;
+
+    fn part(&self, part: Part) -> Result<Answer, Box<dyn Error>> {
+        self.explain(part).map(|explained| explained.answer)
+    }
+
+    fn animate(&self, part: Part, animator: &mut Animator) -> Result<(), Box<dyn Error>>;
+    fn images(&self, part: Part) -> Result<Vec<Image>, Box<dyn Error>>;
+    fn graph(&self, part: Part) -> Result<Dot, Box<dyn Error>>;
+}
+
+struct Parsed<S: Solution> {
+    input: S::Input,
+    params: Params,
+}
+
+impl<S: Solution> ParsedInput for Parsed<S> {
+    fn explain(&self, part: Part) -> Result<Explained<Answer>, Box<dyn Error>> {
+        match part {
+            Part::One => S::part1(&self.input, &self.params).map(IntoExplained::into_explained),
+            Part::Two => S::part2(&self.input, &self.params).map(IntoExplained::into_explained),
+        }
+    }
+
+    fn animate(&self, part: Part, animator: &mut Animator) -> Result<(), Box<dyn Error>> {
+        S::animate(&self.input, &self.params, part, animator)
+    }
+
+    fn images(&self, part: Part) -> Result<Vec<Image>, Box<dyn Error>> {
+        S::images(&self.input, &self.params, part)
+    }
+
+    fn graph(&self, part: Part) -> Result<Dot, Box<dyn Error>> {
+        S::graph(&self.input, &self.params, part)
+    }
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync + 'static,
+    S::Input: 'static,
+{
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn params(&self) -> &'static [Param] {
+        S::PARAMS
+    }
+
+    fn parse_with(
+        &self,
+        input: &str,
+        params: Params,
+    ) -> Result<Box<dyn ParsedInput>, Box<dyn Error>> {
+        let input = crate::parse::normalize(input);
+        let parsed = S::parse(&input).map_err(|err| match err.downcast::<AocError>() {
+            Ok(err) => Box::new(err.in_day(S::DAY)),
+            Err(err) => err,
+        })?;
+        Ok(Box::new(Parsed::<S> {
+            input: parsed,
+            params,
+        }))
+    }
+}
@@ -0,0 +1,245 @@
+use crate::grid::Grid;
+use crate::point::Point2;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt;
+
+/// The smallest rectangle holding some points, corners included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Point2,
+    pub max: Point2,
+}
+
+impl Bounds {
+    fn around(point: Point2) -> Bounds {
+        Bounds {
+            min: point,
+            max: point,
+        }
+    }
+
+    fn extend(self, point: Point2) -> Bounds {
+        Bounds {
+            min: Point2::new(self.min.x.min(point.x), self.min.y.min(point.y)),
+            max: Point2::new(self.max.x.max(point.x), self.max.y.max(point.y)),
+        }
+    }
+
+    fn on_edge(&self, point: Point2) -> bool {
+        point.x == self.min.x
+            || point.x == self.max.x
+            || point.y == self.min.y
+            || point.y == self.max.y
+    }
+
+    pub fn width(&self) -> i64 {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> i64 {
+        self.max.y - self.min.y + 1
+    }
+
+    pub fn area(&self) -> i64 {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, point: Point2) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+}
+
+/// An unbounded grid that only stores the cells that are set, for simulations that spread out
+/// without limit. Tracks the bounding box of its cells as they come and go.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point2, T>,
+    // `None` until asked for after a removal from the edge, since working it out again means
+    // looking at every cell
+    bounds: Cell<Option<Bounds>>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: Cell::new(None),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    /// The cells of `grid` for which `keep` holds, at the same positions.
+    pub fn from_grid(grid: &Grid<T>, mut keep: impl FnMut(&T) -> bool) -> SparseGrid<T>
+    where
+        T: Clone,
+    {
+        grid.iter()
+            .filter(|(_, cell)| keep(cell))
+            .map(|(pos, cell)| (pos, cell.clone()))
+            .collect()
+    }
+
+    /// A dense grid covering the bounding box, with `empty` wherever no cell is set. The top left
+    /// corner of the bounding box becomes `(0, 0)`.
+    pub fn to_grid(&self, empty: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let Some(bounds) = self.bounds() else {
+            return Grid::from_cells(0, 0, vec![]);
+        };
+        let mut grid = Grid::new(bounds.width() as usize, bounds.height() as usize, empty);
+        for (&pos, cell) in self.cells.iter() {
+            grid[pos - bounds.min] = cell.clone();
+        }
+        grid
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, pos: Point2) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn get(&self, pos: Point2) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Point2) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    /// Set the cell at `pos`, returning what was there.
+    pub fn insert(&mut self, pos: Point2, value: T) -> Option<T> {
+        if self.cells.is_empty() {
+            self.bounds.set(Some(Bounds::around(pos)));
+        } else if let Some(bounds) = self.bounds.get() {
+            self.bounds.set(Some(bounds.extend(pos)));
+        }
+        self.cells.insert(pos, value)
+    }
+
+    /// Clear the cell at `pos`, returning what was there.
+    pub fn remove(&mut self, pos: Point2) -> Option<T> {
+        let removed = self.cells.remove(&pos)?;
+        if self.bounds.get().is_some_and(|bounds| bounds.on_edge(pos)) {
+            self.bounds.set(None);
+        }
+        Some(removed)
+    }
+
+    /// The smallest rectangle holding every cell, or `None` if there are none.
+    pub fn bounds(&self) -> Option<Bounds> {
+        if self.bounds.get().is_none() {
+            let mut positions = self.cells.keys();
+            let first = Bounds::around(*positions.next()?);
+            self.bounds
+                .set(Some(positions.fold(first, |b, &pos| b.extend(pos))));
+        }
+        self.bounds.get()
+    }
+
+    /// Every cell with its position, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.cells.iter().map(|(&pos, cell)| (pos, cell))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Point2> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// The cells that are set next to `pos`, north first and going clockwise.
+    pub fn neighbours4(&self, pos: Point2) -> impl Iterator<Item = (Point2, &T)> {
+        pos.neighbours4()
+            .filter_map(|pos| self.get(pos).map(|cell| (pos, cell)))
+    }
+
+    /// The cells that are set around `pos`, diagonals included.
+    pub fn neighbours8(&self, pos: Point2) -> impl Iterator<Item = (Point2, &T)> {
+        pos.neighbours8()
+            .filter_map(|pos| self.get(pos).map(|cell| (pos, cell)))
+    }
+}
+
+impl<T> FromIterator<(Point2, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point2, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        for (pos, cell) in iter {
+            grid.insert(pos, cell);
+        }
+        grid
+    }
+}
+
+/// The bounding box, one line per row, with `.` where no cell is set.
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Some(bounds) = self.bounds() else {
+            return Ok(());
+        };
+        for y in bounds.min.y..=bounds.max.y {
+            if y > bounds.min.y {
+                writeln!(f)?;
+            }
+            for x in bounds.min.x..=bounds.max.x {
+                match self.get(Point2::new(x, y)) {
+                    Some(cell) => write!(f, "{}", cell)?,
+                    None => write!(f, ".")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds_follow_cells() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        grid.insert(Point2::new(2, 3), 'a');
+        grid.insert(Point2::new(-1, 0), 'b');
+        grid.insert(Point2::new(0, 1), 'c');
+        let bounds = grid.bounds().unwrap();
+        assert_eq!(
+            (bounds.min, bounds.max),
+            (Point2::new(-1, 0), Point2::new(2, 3))
+        );
+        assert_eq!(bounds.area(), 16);
+        assert_eq!(grid.to_string(), "b...\n.c..\n....\n...a");
+
+        grid.remove(Point2::new(2, 3));
+        assert_eq!(grid.bounds().unwrap().max, Point2::new(0, 1));
+        assert_eq!(grid.to_string(), "b.\n.c");
+        assert_eq!(grid.neighbours4(Point2::ORIGIN).count(), 2);
+        assert_eq!(grid.neighbours4(Point2::new(1, 0)).count(), 0);
+        assert_eq!(grid.neighbours8(Point2::new(1, 0)).count(), 1);
+        grid.remove(Point2::new(-1, 0));
+        grid.remove(Point2::new(0, 1));
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn test_dense_round_trip() {
+        let dense = Grid::parse("..#\n#..\n", Ok::<char, String>).unwrap();
+        let sparse = SparseGrid::from_grid(&dense, |&ch| ch == '#');
+        assert_eq!(sparse.len(), 2);
+        assert!(sparse.contains(Point2::new(2, 0)));
+        assert_eq!(sparse.to_grid('.'), dense);
+    }
+}
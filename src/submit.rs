@@ -0,0 +1,299 @@
+use crate::solution::Part;
+use regex::Regex;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// What the puzzle site made of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without saying which way.
+    Wrong,
+    /// Nothing was checked because the last answer was too recent. `wait` is how long the site
+    /// says is left, e.g. `"4m 32s"`.
+    RateLimited {
+        wait: Option<String>,
+    },
+}
+
+impl Verdict {
+    /// Read the verdict out of the HTML page the site answers a submission with.
+    pub fn from_response(html: &str) -> Result<Verdict, String> {
+        let article = Regex::new(r"(?s)<article>(.*?)</article>")
+            .unwrap()
+            .captures(html)
+            .map_or(html, |cap| cap.get(1).unwrap().as_str());
+        let text = Regex::new(r"<[^>]*>").unwrap().replace_all(article, "");
+
+        if text.contains("That's the right answer") {
+            Ok(Verdict::Correct)
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Ok(Verdict::TooHigh)
+            } else if text.contains("your answer is too low") {
+                Ok(Verdict::TooLow)
+            } else {
+                Ok(Verdict::Wrong)
+            }
+        } else if text.contains("You gave an answer too recently") {
+            let wait = Regex::new(r"You have (.*?) left to wait")
+                .unwrap()
+                .captures(&text)
+                .map(|cap| cap[1].to_string());
+            Ok(Verdict::RateLimited { wait })
+        } else if text.contains("You don't seem to be solving the right level") {
+            Err(String::from(
+                "the site won't take an answer for this part; is it already solved?",
+            ))
+        } else {
+            Err(format!("unrecognised response: {}", text.trim()))
+        }
+    }
+
+    fn keyword(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited { .. } => "rate-limited",
+        }
+    }
+
+    fn from_keyword(s: &str) -> Option<Verdict> {
+        match s {
+            "correct" => Some(Verdict::Correct),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            "wrong" => Some(Verdict::Wrong),
+            "rate-limited" => Some(Verdict::RateLimited { wait: None }),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::RateLimited { wait: Some(wait) } => {
+                write!(f, "rate limited, try again in {}", wait)
+            }
+            Verdict::RateLimited { wait: None } => write!(f, "rate limited"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guess {
+    pub part: Part,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Every answer submitted for one day, kept next to its input as `guesses`: one tab separated
+/// `part answer verdict` record per line, with newlines in answers written as `\n` like the
+/// answers manifest.
+#[derive(Debug)]
+pub struct History {
+    pub path: PathBuf,
+    pub guesses: Vec<Guess>,
+}
+
+impl History {
+    /// The history file beside `input`.
+    pub fn path_for(input: &Path) -> PathBuf {
+        input.with_file_name("guesses")
+    }
+
+    /// Load the history at `path`, which is empty if the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<History, Box<dyn Error>> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => Err(format!("could not read {}: {}", path.display(), err))?,
+        };
+        let mut guesses = vec![];
+        for (i, line) in content.lines().enumerate() {
+            let invalid = || {
+                format!(
+                    "{} line {}: invalid guess {:?}",
+                    path.display(),
+                    i + 1,
+                    line
+                )
+            };
+            let [part, answer, verdict] = line.split('\t').collect::<Vec<&str>>()[..] else {
+                return Err(invalid())?;
+            };
+            let part = match part {
+                "1" => Part::One,
+                "2" => Part::Two,
+                _ => return Err(invalid())?,
+            };
+            let verdict = Verdict::from_keyword(verdict).ok_or_else(invalid)?;
+            guesses.push(Guess {
+                part,
+                answer: answer.replace("\\n", "\n"),
+                verdict,
+            });
+        }
+        Ok(History {
+            path: path.to_path_buf(),
+            guesses,
+        })
+    }
+
+    /// Why `answer` shouldn't be sent for `part`, if there's a reason: it's drawn over several
+    /// lines, the part is already solved, the answer was already wrong, or it's outside the
+    /// bounds set by earlier too high or too low guesses.
+    pub fn refuse(&self, part: Part, answer: &str) -> Option<String> {
+        if answer.contains('\n') {
+            return Some(String::from(
+                "the answer is drawn over several lines; read the letters off it and type them in",
+            ));
+        }
+        let guesses = self.guesses.iter().filter(|g| g.part == part);
+        let mut lowest_too_high: Option<i128> = None;
+        let mut highest_too_low: Option<i128> = None;
+        for guess in guesses {
+            match guess.verdict {
+                Verdict::Correct => {
+                    return Some(format!(
+                        "part {} was already solved with {}",
+                        part, guess.answer
+                    ))
+                }
+                Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong if guess.answer == answer => {
+                    return Some(format!("{} was already {}", answer, guess.verdict))
+                }
+                Verdict::TooHigh => {
+                    if let Ok(n) = guess.answer.parse::<i128>() {
+                        lowest_too_high = Some(lowest_too_high.map_or(n, |m| m.min(n)));
+                    }
+                }
+                Verdict::TooLow => {
+                    if let Ok(n) = guess.answer.parse::<i128>() {
+                        highest_too_low = Some(highest_too_low.map_or(n, |m| m.max(n)));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let n = answer.parse::<i128>().ok()?;
+        match (lowest_too_high, highest_too_low) {
+            (Some(high), _) if n >= high => Some(format!("{} was already too high", high)),
+            (_, Some(low)) if n <= low => Some(format!("{} was already too low", low)),
+            _ => None,
+        }
+    }
+
+    /// Add a guess and append it to the file.
+    pub fn record(&mut self, guess: Guess) -> Result<(), Box<dyn Error>> {
+        use std::io::Write;
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|err| format!("could not open {}: {}", self.path.display(), err))?;
+        writeln!(
+            file,
+            "{}\t{}\t{}",
+            guess.part,
+            guess.answer.replace('\n', "\\n"),
+            guess.verdict.keyword()
+        )?;
+        self.guesses.push(guess);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verdict_from_response() {
+        let page = |text: &str| {
+            format!(
+                "<html><main><article><p>{}</p></article></main></html>",
+                text
+            )
+        };
+        assert_eq!(
+            Verdict::from_response(&page(
+                "That's the right answer!  You are <em>one gold star</em> closer."
+            )),
+            Ok(Verdict::Correct)
+        );
+        assert_eq!(
+            Verdict::from_response(&page(
+                "That's not the right answer; your answer is too high.  If you're stuck..."
+            )),
+            Ok(Verdict::TooHigh)
+        );
+        assert_eq!(
+            Verdict::from_response(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Ok(Verdict::TooLow)
+        );
+        assert_eq!(
+            Verdict::from_response(&page("That's not the right answer.  If you're stuck...")),
+            Ok(Verdict::Wrong)
+        );
+        assert_eq!(
+            Verdict::from_response(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 32s left to wait.")),
+            Ok(Verdict::RateLimited { wait: Some(String::from("4m 32s")) })
+        );
+        assert!(
+            Verdict::from_response(&page("You don't seem to be solving the right level.")).is_err()
+        );
+    }
+
+    #[test]
+    fn test_refuse() {
+        let guess = |part, answer: &str, verdict| Guess {
+            part,
+            answer: answer.to_string(),
+            verdict,
+        };
+        let history = History {
+            path: PathBuf::from("guesses"),
+            guesses: vec![
+                guess(Part::One, "100", Verdict::TooHigh),
+                guess(Part::One, "40", Verdict::TooLow),
+                guess(Part::One, "70", Verdict::Wrong),
+                guess(Part::One, "75", Verdict::RateLimited { wait: None }),
+                guess(Part::Two, "ABC", Verdict::Correct),
+            ],
+        };
+        assert!(history.refuse(Part::One, "70").is_some());
+        assert!(history.refuse(Part::One, "100").is_some());
+        assert!(history.refuse(Part::One, "250").is_some());
+        assert!(history.refuse(Part::One, "12").is_some());
+        assert_eq!(history.refuse(Part::One, "75"), None);
+        assert_eq!(history.refuse(Part::One, "abc"), None);
+        assert!(history.refuse(Part::Two, "XYZ").is_some());
+    }
+
+    #[test]
+    fn test_refuse_drawing() {
+        let history = History {
+            path: PathBuf::from("guesses"),
+            guesses: vec![],
+        };
+        let drawing = "#..#\n####\n#..#\n";
+        assert!(history.refuse(Part::Two, drawing).is_some());
+        assert_eq!(history.refuse(Part::Two, "H"), None);
+    }
+}
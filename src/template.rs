@@ -0,0 +1,223 @@
+//! Parsing lines (or whole paragraphs) laid out like a template, e.g.
+//! `"move {num} from {from} to {to}"`, into typed fields.
+//!
+//! A field takes everything up to the text that follows it in the template, and never runs past
+//! the end of a line; a field at the very end takes the rest of the line. Two fields can't be
+//! next to each other, and `{{` and `}}` stand for literal braces. Templates are meant to be
+//! built once, in a `LazyLock`, and matched against every line:
+//!
+//! ```text
+//! static COMMAND: LazyLock<Template> =
+//!     LazyLock::new(|| Template::new("move {num} from {from} to {to}"));
+//!
+//! let fields = COMMAND.matches(line)?;
+//! let num: usize = fields.get("num")?;
+//! ```
+use crate::error::AocError;
+use crate::parse::Span;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Piece {
+    Literal(String),
+    Field(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    pieces: Vec<Piece>,
+}
+
+impl Template {
+    /// Compile `pattern`. Templates are written in the source rather than read from input, so a
+    /// malformed one panics.
+    pub fn new(pattern: &str) -> Template {
+        let mut pieces = vec![];
+        let mut literal = String::new();
+        let mut chars = pattern.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let name: String = chars.by_ref().take_while(|&ch| ch != '}').collect();
+                    assert!(
+                        !name.is_empty()
+                            && name.chars().all(|ch| ch.is_alphanumeric() || ch == '_'),
+                        "bad field name {:?} in template {:?}",
+                        name,
+                        pattern
+                    );
+                    if literal.is_empty() {
+                        assert!(
+                            !matches!(pieces.last(), Some(Piece::Field(_))),
+                            "fields must be separated by some text in template {:?}",
+                            pattern
+                        );
+                    } else {
+                        pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+                    }
+                    pieces.push(Piece::Field(name));
+                }
+                '}' => panic!("unmatched '}}' in template {:?}", pattern),
+                _ => literal.push(ch),
+            }
+        }
+        if !literal.is_empty() {
+            pieces.push(Piece::Literal(literal));
+        }
+        Template { pieces }
+    }
+
+    /// The names of the fields, in order.
+    pub fn fields(&self) -> impl Iterator<Item = &str> {
+        self.pieces.iter().filter_map(|piece| match piece {
+            Piece::Field(name) => Some(name.as_str()),
+            Piece::Literal(_) => None,
+        })
+    }
+
+    /// Match the whole of `span` against the template. Errors point at the first place the text
+    /// and the template part ways.
+    pub fn matches<'t, 'a>(&'t self, span: Span<'a>) -> Result<Fields<'t, 'a>, AocError> {
+        let text = span.as_str();
+        let mut fields = vec![];
+        let mut pos = 0;
+        for (i, piece) in self.pieces.iter().enumerate() {
+            let rest = &text[pos..];
+            let line_end = rest.find('\n').unwrap_or(rest.len());
+            match piece {
+                Piece::Literal(literal) => {
+                    if !rest.starts_with(literal.as_str()) {
+                        // point at the first character that differs
+                        let same = rest
+                            .char_indices()
+                            .zip(literal.chars())
+                            .take_while(|&((_, a), b)| a == b)
+                            .last()
+                            .map_or(0, |((i, ch), _)| i + ch.len_utf8());
+                        let at = &rest[same..];
+                        let at = &at[..at.chars().next().map_or(0, char::len_utf8)];
+                        return Err(span.within(at).error(format!("expected {:?}", literal)));
+                    }
+                    pos += literal.len();
+                }
+                Piece::Field(name) => {
+                    let len = match self.pieces.get(i + 1) {
+                        Some(Piece::Literal(next)) => match rest.find(next.as_str()) {
+                            Some(len) if len <= line_end => len,
+                            _ => {
+                                let message = format!("expected {:?}", next);
+                                return Err(span.within(&rest[line_end..line_end]).error(message));
+                            }
+                        },
+                        _ => line_end,
+                    };
+                    let value = &rest[..len];
+                    if value.trim().is_empty() {
+                        let message = format!("expected a value for {}", name);
+                        return Err(span.within(value).error(message));
+                    }
+                    fields.push((name.as_str(), span.within(value)));
+                    pos += len;
+                }
+            }
+        }
+        if pos < text.len() {
+            let extra = text[pos..].trim_end();
+            return Err(span.within(extra).error("unexpected text"));
+        }
+        Ok(Fields { fields })
+    }
+}
+
+/// The fields of a line that matched a `Template`.
+#[derive(Debug, Clone)]
+pub struct Fields<'t, 'a> {
+    fields: Vec<(&'t str, Span<'a>)>,
+}
+
+impl<'t, 'a> Fields<'t, 'a> {
+    /// The text of field `name`. Panics if the template has no such field.
+    pub fn span(&self, name: &str) -> Span<'a> {
+        match self.fields.iter().find(|(field, _)| *field == name) {
+            Some(&(_, span)) => span,
+            None => panic!("the template has no field {:?}", name),
+        }
+    }
+
+    pub fn str(&self, name: &str) -> &'a str {
+        self.span(name).as_str()
+    }
+
+    /// Field `name` parsed as a `T`, with any error pointing at it.
+    pub fn get<T>(&self, name: &str) -> Result<T, AocError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.span(name).parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        let template = Template::new("move {num} from {from} to {to}");
+        assert_eq!(template.fields().collect::<Vec<_>>(), ["num", "from", "to"]);
+
+        let content = "move 3 from 1 to 20\nmove 3 form 1 to 2\nmove x from 1 to 2\nmove 3 from 1";
+        let lines = Span::new(content).lines().collect::<Vec<_>>();
+        let fields = template.matches(lines[0]).unwrap();
+        assert_eq!(fields.get::<usize>("num"), Ok(3));
+        assert_eq!(fields.get::<usize>("to"), Ok(20));
+
+        let err = template.matches(lines[1]).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 19));
+        assert_eq!(err.message, "expected \" from \"");
+
+        let fields = template.matches(lines[2]).unwrap();
+        let err = fields.get::<usize>("num").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 6));
+
+        let err = template.matches(lines[3]).unwrap_err();
+        assert_eq!((err.line, err.column), (4, 14));
+        assert_eq!(err.message, "expected \" to \"");
+    }
+
+    #[test]
+    fn test_literals_and_lines() {
+        let template = Template::new("{{{name}}}:\n  {value}");
+        let content = "{a}:\n  1, 2\n";
+        let fields = template.matches(Span::new(content.trim_end())).unwrap();
+        assert_eq!(fields.str("name"), "a");
+        assert_eq!(fields.str("value"), "1, 2");
+
+        // fields don't run on to the next line looking for what follows them
+        let err = Template::new("{a} b\n{c}")
+            .matches(Span::new("a\nb b\nc"))
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (1, 2));
+
+        let err = Template::new("noop")
+            .matches(Span::new("noop 3"))
+            .unwrap_err();
+        assert_eq!(err.message, "unexpected text");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_adjacent_fields() {
+        Template::new("{a}{b}");
+    }
+}
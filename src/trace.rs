@@ -0,0 +1,215 @@
+//! Leveled debug output for solutions. Events go to stderr, so answers on stdout stay clean, and
+//! are dropped unless the runner was asked for them with `-v` (debug) or `-vv` (trace).
+//!
+//! ```text
+//! trace::debug!("new best", row = pos.y, col = pos.x, score = this);
+//! trace::trace!("rope", rope = %draw_rope(&rope));
+//! ```
+//!
+//! Fields are written with `Debug`, or with `Display` when prefixed by `%`. Nothing is formatted
+//! when the level is off.
+use serde_json::Value;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    /// Things worth knowing about a run as a whole, a handful of lines per part.
+    Debug,
+    /// Every step: one event per line of input, per round, per tick.
+    Trace,
+}
+
+impl Level {
+    /// The level for `-v` given `n` times.
+    pub fn from_verbosity(n: usize) -> Level {
+        match n {
+            0 => Level::Off,
+            1 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Level::Off => "OFF",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// `DEBUG d08: new best row=3 col=2 score=8`
+    Text,
+    /// One JSON object per line, with `level`, `target`, `message` and the fields.
+    Json,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+static JSON: AtomicBool = AtomicBool::new(false);
+
+/// Start emitting events up to `level`, for the rest of the process.
+pub fn init(level: Level, format: Format) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+    JSON.store(format == Format::Json, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    level != Level::Off && level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+/// A field's value, already formatted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldValue {
+    Debug(String),
+    Display(String),
+}
+
+impl FieldValue {
+    fn text(&self) -> &str {
+        match self {
+            FieldValue::Debug(s) | FieldValue::Display(s) => s,
+        }
+    }
+
+    fn json(&self) -> Value {
+        match self {
+            // numbers, bools and strings already read as JSON in their Debug form
+            FieldValue::Debug(s) => {
+                serde_json::from_str(s).unwrap_or_else(|_| Value::String(s.clone()))
+            }
+            FieldValue::Display(s) => Value::String(s.clone()),
+        }
+    }
+}
+
+/// The event as a line of text, without the newline. Multi-line values start on a line of their
+/// own.
+pub fn format_text(
+    level: Level,
+    target: &str,
+    message: &str,
+    fields: &[(&str, FieldValue)],
+) -> String {
+    let target = target.rsplit("::").next().unwrap_or(target);
+    let mut line = format!("{} {}: {}", level.name(), target, message);
+    for (key, value) in fields {
+        let value = value.text();
+        if value.contains('\n') {
+            line += &format!(" {}=\n{}", key, value);
+        } else {
+            line += &format!(" {}={}", key, value);
+        }
+    }
+    line
+}
+
+/// The event as a JSON object on one line.
+pub fn format_json(
+    level: Level,
+    target: &str,
+    message: &str,
+    fields: &[(&str, FieldValue)],
+) -> String {
+    // built by hand rather than through a `Map`, which would sort the fields by name
+    let quote = |s: &str| Value::from(s).to_string();
+    let mut line = format!(
+        "{{\"level\":{},\"target\":{},\"message\":{}",
+        quote(level.name()),
+        quote(target),
+        quote(message)
+    );
+    for (key, value) in fields {
+        line += &format!(",{}:{}", quote(key), value.json());
+    }
+    line + "}"
+}
+
+/// Write an event to stderr. Call it through `debug!` or `trace!`, which check the level first.
+pub fn emit(level: Level, target: &str, message: &str, fields: &[(&str, FieldValue)]) {
+    let line = if JSON.load(Ordering::Relaxed) {
+        format_json(level, target, message, fields)
+    } else {
+        format_text(level, target, message, fields)
+    };
+    // losing debug output isn't worth failing a run over
+    let _ = writeln!(std::io::stderr().lock(), "{}", line);
+}
+
+macro_rules! event {
+    ($level:expr, $message:expr $(, $($field:tt)*)?) => {
+        if $crate::trace::enabled($level) {
+            #[allow(unused_mut)]
+            let mut fields: Vec<(&str, $crate::trace::FieldValue)> = vec![];
+            $($crate::trace::fields!(fields; $($field)*);)?
+            $crate::trace::emit($level, module_path!(), $message, &fields);
+        }
+    };
+}
+
+macro_rules! fields {
+    ($fields:ident;) => {};
+    ($fields:ident; $key:ident = %$value:expr $(, $($rest:tt)*)?) => {
+        $fields.push((
+            stringify!($key),
+            $crate::trace::FieldValue::Display($value.to_string()),
+        ));
+        $($crate::trace::fields!($fields; $($rest)*);)?
+    };
+    ($fields:ident; $key:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $fields.push((
+            stringify!($key),
+            $crate::trace::FieldValue::Debug(format!("{:?}", $value)),
+        ));
+        $($crate::trace::fields!($fields; $($rest)*);)?
+    };
+}
+
+macro_rules! debug {
+    ($($args:tt)*) => {
+        $crate::trace::event!($crate::trace::Level::Debug, $($args)*)
+    };
+}
+
+macro_rules! trace {
+    ($($args:tt)*) => {
+        $crate::trace::event!($crate::trace::Level::Trace, $($args)*)
+    };
+}
+
+pub(crate) use debug;
+pub(crate) use event;
+pub(crate) use fields;
+pub(crate) use trace;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let fields = [
+            ("n", FieldValue::Debug(format!("{:?}", 16))),
+            ("item", FieldValue::Debug(format!("{:?}", 'p'))),
+            ("name", FieldValue::Debug(format!("{:?}", "abc"))),
+            ("grid", FieldValue::Display(String::from("#.\n.#"))),
+        ];
+        assert_eq!(
+            format_text(Level::Trace, "advent_of_code_2022::d03", "found", &fields),
+            "TRACE d03: found n=16 item='p' name=\"abc\" grid=\n#.\n.#"
+        );
+        assert_eq!(
+            format_json(Level::Debug, "advent_of_code_2022::d03", "found", &fields),
+            concat!(
+                r#"{"level":"DEBUG","target":"advent_of_code_2022::d03","message":"found","#,
+                r##""n":16,"item":"'p'","name":"abc","grid":"#.\n.#"}"##
+            )
+        );
+        assert!(!enabled(Level::Off));
+        assert_eq!(Level::from_verbosity(0), Level::Off);
+        assert_eq!(Level::from_verbosity(3), Level::Trace);
+    }
+}
@@ -0,0 +1,17 @@
+use advent_of_code_2022::answers::{self, Manifest, Status};
+
+#[test]
+fn test_known_answers() {
+    let manifest = Manifest::load(&Manifest::default_path()).unwrap();
+    let results = answers::check(&manifest);
+    for result in &results {
+        println!("{}", result);
+    }
+
+    let failed: Vec<String> = results
+        .iter()
+        .filter(|r| r.status == Status::Fail)
+        .map(|r| r.to_string())
+        .collect();
+    assert!(failed.is_empty(), "wrong answers:\n{}", failed.join("\n"));
+}